@@ -0,0 +1,210 @@
+// timeshot_core/src/cache/mod.rs
+//
+// Cache persistant des analyses : un fichier JSON unique, une entrée par chemin,
+// valable tant que la taille et la date de modification du fichier n'ont pas changé.
+
+use crate::hash::compute::HashAlgorithm;
+use crate::types::{ExifData, FileAnalysis};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const CACHE_FILE_NAME: &str = "analysis_cache.json";
+/// 2 : hash perceptuel calculé sur la miniature EXIF quand elle existe.
+const CACHE_VERSION: u32 = 2;
+
+/// Données d'analyse mises en cache pour un fichier.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub file_size: u64,
+    pub modified: SystemTime,
+    pub exif: ExifData,
+    pub file_hash: Option<String>,
+    /// Algorithme de `file_hash` (BLAKE3 pour les entrées écrites avant son ajout).
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
+    pub perceptual_hash: Option<u64>,
+    /// Distingue "pas une image" de "hash perceptuel jamais calculé".
+    pub perceptual_computed: bool,
+}
+
+#[derive(Deserialize)]
+struct CacheFile {
+    version: u32,
+    entries: HashMap<String, CacheEntry>,
+}
+
+#[derive(Serialize)]
+struct CacheFileRef<'a> {
+    version: u32,
+    entries: &'a HashMap<String, CacheEntry>,
+}
+
+#[derive(Debug)]
+pub struct AnalysisCache {
+    path: PathBuf,
+    // Clé : chemin complet (converti en texte pour le format JSON)
+    entries: HashMap<String, CacheEntry>,
+}
+
+fn cache_key(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+impl AnalysisCache {
+    /// Charge le cache ; un fichier absent, illisible ou d'une autre version donne un cache vide.
+    pub fn open<P: Into<PathBuf>>(path: P) -> Self {
+        let path = path.into();
+        let entries = File::open(&path)
+            .ok()
+            .and_then(|file| serde_json::from_reader::<_, CacheFile>(BufReader::new(file)).ok())
+            .filter(|cache| cache.version == CACHE_VERSION)
+            .map(|cache| cache.entries)
+            .unwrap_or_default();
+        AnalysisCache { path, entries }
+    }
+
+    /// Chemin par défaut dans le dossier de cache utilisateur (ex: `~/.cache/TimeShotRenamer/analysis_cache.json`).
+    pub fn default_path() -> io::Result<PathBuf> {
+        let cache_dir = dirs::cache_dir().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "Dossier de cache utilisateur introuvable")
+        })?;
+        Ok(cache_dir.join("TimeShotRenamer").join(CACHE_FILE_NAME))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entrée valide pour ce fichier, si sa taille et sa date de modification correspondent.
+    pub fn lookup(&self, path: &Path, file_size: u64, modified: SystemTime) -> Option<&CacheEntry> {
+        self.entries
+            .get(&cache_key(path))
+            .filter(|entry| entry.file_size == file_size && entry.modified == modified)
+    }
+
+    /// Enregistre (ou remplace) l'entrée d'un fichier analysé.
+    pub fn update(&mut self, analysis: &FileAnalysis, modified: SystemTime, perceptual_computed: bool) {
+        let entry = CacheEntry {
+            file_size: analysis.file_size,
+            modified,
+            exif: analysis.exif.clone(),
+            file_hash: analysis.file_hash.clone(),
+            hash_algorithm: analysis.hash_algorithm,
+            perceptual_hash: analysis.perceptual_hash,
+            perceptual_computed,
+        };
+        self.entries.insert(cache_key(&analysis.full_original_path), entry);
+    }
+
+    /// Complète le hash d'une entrée existante (calculé après coup, lors de la recherche de doublons).
+    pub fn set_file_hash(&mut self, path: &Path, file_hash: &str, algorithm: HashAlgorithm) {
+        if let Some(entry) = self.entries.get_mut(&cache_key(path)) {
+            entry.file_hash = Some(file_hash.to_string());
+            entry.hash_algorithm = algorithm;
+        }
+    }
+
+    /// Retire l'entrée d'un fichier (déplacé, renommé...).
+    pub fn invalidate(&mut self, path: &Path) {
+        self.entries.remove(&cache_key(path));
+    }
+
+    /// Vide toutes les entrées (le fichier est réécrit au prochain `save`).
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Supprime les entrées des fichiers qui n'existent plus.
+    pub fn prune_missing(&mut self) {
+        self.entries.retain(|path, _| Path::new(path).is_file());
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = self.path.with_extension("json.tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            let cache = CacheFileRef { version: CACHE_VERSION, entries: &self.entries };
+            serde_json::to_writer(&mut writer, &cache)?;
+            writer.flush()?;
+        }
+        fs::rename(tmp_path, &self.path)
+    }
+}
+
+/// Supprime le fichier de cache (aucune erreur s'il n'existe pas).
+pub fn delete_cache_file(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{file_analysis, test_dir};
+    use std::time::Duration;
+
+    #[test]
+    fn lookup_hits_only_while_size_and_mtime_are_unchanged() {
+        let dir = test_dir("cache_lookup");
+        let photo = dir.join("photo.jpg");
+        fs::write(&photo, "abc").unwrap();
+        let modified = fs::metadata(&photo).unwrap().modified().unwrap();
+        let analysis = FileAnalysis { file_size: 3, file_hash: Some("h".to_string()), ..file_analysis(&photo) };
+
+        let mut cache = AnalysisCache::open(dir.join("cache.json"));
+        assert!(cache.is_empty());
+        cache.update(&analysis, modified, true);
+        cache.save().unwrap();
+
+        let cache = AnalysisCache::open(dir.join("cache.json"));
+        let entry = cache.lookup(&photo, 3, modified).expect("entrée valide");
+        assert_eq!(entry.file_hash.as_deref(), Some("h"));
+        assert!(entry.perceptual_computed);
+        assert!(cache.lookup(&photo, 4, modified).is_none());
+        assert!(cache.lookup(&photo, 3, modified + Duration::from_secs(1)).is_none());
+        assert!(cache.lookup(&dir.join("autre.jpg"), 3, modified).is_none());
+    }
+
+    #[test]
+    fn prune_missing_drops_entries_of_deleted_files() {
+        let dir = test_dir("cache_prune");
+        let (kept, deleted) = (dir.join("garde.jpg"), dir.join("supprime.jpg"));
+        let mut cache = AnalysisCache::open(dir.join("cache.json"));
+        for path in [&kept, &deleted] {
+            fs::write(path, "x").unwrap();
+            cache.update(&file_analysis(path), SystemTime::UNIX_EPOCH, false);
+        }
+        fs::remove_file(&deleted).unwrap();
+        cache.prune_missing();
+        assert_eq!(cache.len(), 1);
+        assert!(cache.lookup(&kept, 0, SystemTime::UNIX_EPOCH).is_some());
+        assert!(cache.lookup(&deleted, 0, SystemTime::UNIX_EPOCH).is_none());
+    }
+
+    #[test]
+    fn other_versions_open_as_an_empty_cache() {
+        let dir = test_dir("cache_version");
+        let path = dir.join("cache.json");
+        fs::write(&path, format!("{{\"version\":{},\"entries\":{{}}}}", CACHE_VERSION + 1)).unwrap();
+        assert!(AnalysisCache::open(&path).is_empty());
+        fs::write(&path, "pas du JSON").unwrap();
+        assert!(AnalysisCache::open(&path).is_empty());
+    }
+}
@@ -1,3 +1,5 @@
 pub mod reader;
 pub mod model;
 pub mod analyze;
+pub mod writer;
+pub mod thumbnail;
@@ -1 +1,98 @@
-// Pour l’instant vide, utilisé si besoin de struct spécifiques supplémentaires
\ No newline at end of file
+// timeshot_core/src/exif/model.rs
+//
+// Présentation des métadonnées : regroupement des champs EXIF par catégorie pour l'affichage.
+
+use crate::types::ExifData;
+use chrono::NaiveDateTime;
+
+/// Catégories d'affichage des métadonnées, dans leur ordre d'affichage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MetadataCategory {
+    Dates,
+    Camera,
+    Exposure,
+    Gps,
+    Descriptive,
+    Other,
+}
+
+impl MetadataCategory {
+    pub const ALL: [MetadataCategory; 6] = [
+        MetadataCategory::Dates, MetadataCategory::Camera, MetadataCategory::Exposure,
+        MetadataCategory::Gps, MetadataCategory::Descriptive, MetadataCategory::Other,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MetadataCategory::Dates => "Dates",
+            MetadataCategory::Camera => "Appareil",
+            MetadataCategory::Exposure => "Exposition",
+            MetadataCategory::Gps => "GPS",
+            MetadataCategory::Descriptive => "Description",
+            MetadataCategory::Other => "Autres",
+        }
+    }
+}
+
+const CAMERA_TAGS: [&str; 9] = ["Make", "Model", "LensMake", "LensModel", "LensSpecification", "BodySerialNumber", "LensSerialNumber", "Software", "CameraOwnerName"];
+const EXPOSURE_TAGS: [&str; 19] = [
+    "ExposureTime", "FNumber", "ExposureProgram", "PhotographicSensitivity", "ISOSpeed", "ShutterSpeedValue",
+    "ApertureValue", "BrightnessValue", "ExposureBiasValue", "MaxApertureValue", "MeteringMode", "LightSource",
+    "Flash", "FocalLength", "FocalLengthIn35mmFilm", "ExposureMode", "WhiteBalance", "SceneCaptureType", "DigitalZoomRatio",
+];
+const DESCRIPTIVE_TAGS: [&str; 5] = ["Artist", "Copyright", "ImageDescription", "UserComment", "DocumentName"];
+
+/// Catégorie d'un tag d'après son nom (clé de `other_fields`, ex: "ExposureTime (IFDprimary)").
+pub fn categorize_tag(key: &str) -> MetadataCategory {
+    let name = key.split(" (").next().unwrap_or(key);
+    if name.starts_with("GPS") {
+        MetadataCategory::Gps
+    } else if name.starts_with("DateTime") || name.starts_with("OffsetTime") || name.starts_with("SubSecTime") {
+        MetadataCategory::Dates
+    } else if CAMERA_TAGS.contains(&name) {
+        MetadataCategory::Camera
+    } else if EXPOSURE_TAGS.contains(&name) {
+        MetadataCategory::Exposure
+    } else if DESCRIPTIVE_TAGS.contains(&name) {
+        MetadataCategory::Descriptive
+    } else {
+        MetadataCategory::Other
+    }
+}
+
+/// Tous les champs renseignés, regroupés par catégorie (catégories vides omises, champs triés par nom).
+pub fn grouped_fields(exif: &ExifData) -> Vec<(MetadataCategory, Vec<(String, String)>)> {
+    let date = |d: NaiveDateTime| d.format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut fields: Vec<(MetadataCategory, String, String)> = Vec::new();
+    let mut push = |category, name: &str, value: Option<String>| {
+        if let Some(value) = value.filter(|v| !v.is_empty()) { fields.push((category, name.to_string(), value)); }
+    };
+    push(MetadataCategory::Dates, "Date de prise de vue", exif.date_taken.map(date));
+    push(MetadataCategory::Dates, "Date de numérisation", exif.create_date.map(date));
+    push(MetadataCategory::Dates, "Date de modification", exif.modify_date.map(date));
+    push(MetadataCategory::Camera, "Modèle", exif.camera_model.clone());
+    push(MetadataCategory::Camera, "Objectif", exif.lens_model.clone());
+    push(MetadataCategory::Descriptive, "Auteur", exif.artist.clone());
+    push(MetadataCategory::Descriptive, "Titre", exif.title.clone());
+    push(MetadataCategory::Descriptive, "Description", exif.description.clone());
+    push(MetadataCategory::Descriptive, "Mots-clés", Some(exif.keywords.join(", ")));
+
+    let mut others: Vec<(&String, &String)> = exif.other_fields.iter().collect();
+    others.sort();
+    for (key, value) in others {
+        fields.push((categorize_tag(key), key.clone(), value.clone()));
+    }
+
+    MetadataCategory::ALL
+        .into_iter()
+        .map(|category| {
+            let entries: Vec<(String, String)> = fields
+                .iter()
+                .filter(|(c, _, _)| *c == category)
+                .map(|(_, name, value)| (name.clone(), value.clone()))
+                .collect();
+            (category, entries)
+        })
+        .filter(|(_, entries)| !entries.is_empty())
+        .collect()
+}
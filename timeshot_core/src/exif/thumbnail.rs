@@ -0,0 +1,61 @@
+// timeshot_core/src/exif/thumbnail.rs
+//
+// Vignettes d'aperçu : miniature JPEG intégrée aux EXIF quand elle suffit (lecture rapide),
+// sinon décodage complet de l'image. L'orientation EXIF est appliquée dans les deux cas.
+
+use exif::{In, Reader as KamadakReader, Tag};
+use image::imageops::FilterType;
+use image::metadata::Orientation;
+use image::DynamicImage;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// Image RGBA 8 bits prête à l'affichage.
+#[derive(Debug, Clone)]
+pub struct Thumbnail {
+    pub width: u32,
+    pub height: u32,
+    /// Pixels RGBA, ligne par ligne (`width * 4` octets par ligne).
+    pub rgba: Vec<u8>,
+}
+
+/// Miniature JPEG intégrée (IFD1) et orientation EXIF du fichier.
+fn read_embedded(path: &Path) -> (Option<Vec<u8>>, Option<u32>) {
+    let Ok(file) = File::open(path) else { return (None, None) };
+    let Ok(exif) = KamadakReader::new().read_from_container(&mut BufReader::new(file)) else { return (None, None) };
+    let orientation = exif.get_field(Tag::Orientation, In::PRIMARY).and_then(|f| f.value.get_uint(0));
+    let offset = exif.get_field(Tag::JPEGInterchangeFormat, In::THUMBNAIL).and_then(|f| f.value.get_uint(0));
+    let length = exif.get_field(Tag::JPEGInterchangeFormatLength, In::THUMBNAIL).and_then(|f| f.value.get_uint(0));
+    let thumbnail = match (offset, length) {
+        (Some(offset), Some(length)) => exif.buf().get(offset as usize..(offset as usize).saturating_add(length as usize)).map(<[u8]>::to_vec),
+        _ => None,
+    };
+    (thumbnail, orientation)
+}
+
+/// Renvoie les octets JPEG de la miniature intégrée aux EXIF, s'il y en a une.
+pub fn extract_embedded_thumbnail(path: &Path) -> Option<Vec<u8>> {
+    read_embedded(path).0
+}
+
+/// Charge une vignette tenant dans un carré de `max_size` pixels.
+/// La miniature EXIF est utilisée si elle est assez grande, sinon l'image entière est décodée.
+pub fn load_thumbnail(path: &Path, max_size: u32) -> Result<Thumbnail, String> {
+    let (embedded, orientation) = read_embedded(path);
+    let embedded = embedded
+        .and_then(|bytes| image::load_from_memory(&bytes).ok())
+        .filter(|image| image.width().max(image.height()) >= max_size);
+    let mut image: DynamicImage = match embedded {
+        Some(image) => image,
+        None => image::open(path).map_err(|e| format!("Décodage impossible de {:?}: {}", path, e))?,
+    };
+    if let Some(orientation) = orientation.and_then(|o| u8::try_from(o).ok()).and_then(Orientation::from_exif) {
+        image.apply_orientation(orientation);
+    }
+    if image.width().max(image.height()) > max_size {
+        image = image.resize(max_size, max_size, FilterType::Triangle);
+    }
+    let rgba = image.to_rgba8();
+    Ok(Thumbnail { width: rgba.width(), height: rgba.height(), rgba: rgba.into_raw() })
+}
@@ -0,0 +1,554 @@
+// timeshot_core/src/exif/writer.rs
+//
+// Écriture de `DateTimeOriginal` / `OffsetTimeOriginal`.
+// On ne réécrit jamais la structure EXIF : si les champs existent déjà dans un JPEG ou un TIFF,
+// leur valeur ASCII est remplacée sur place (même taille). Sinon, on écrit un sidecar XMP
+// (la date est ajoutée à un sidecar existant, dont le reste du contenu est conservé).
+
+use crate::journal::entry::Operation;
+use crate::journal::store::{new_batch_id, Journal};
+use chrono::{FixedOffset, NaiveDateTime};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+const TAG_EXIF_IFD_POINTER: u16 = 0x8769;
+const TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
+const TAG_OFFSET_TIME_ORIGINAL: u16 = 0x9011;
+const TYPE_ASCII: u16 = 2;
+const MAX_IFD_ENTRIES: u16 = 1000;
+/// Longueur de "AAAA:MM:JJ HH:MM:SS" et de "+HH:MM" (sans le NUL final).
+const DATE_VALUE_LEN: u32 = 19;
+const OFFSET_VALUE_LEN: u32 = 6;
+
+const XMP_NS_EXIF: &str = "http://ns.adobe.com/exif/1.0/";
+const XMP_NS_PHOTOSHOP: &str = "http://ns.adobe.com/photoshop/1.0/";
+
+/// Date à écrire pour un fichier.
+#[derive(Debug, Clone)]
+pub struct DateWrite {
+    pub path: PathBuf,
+    pub date: NaiveDateTime,
+    pub offset: Option<FixedOffset>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateWriteMethod {
+    /// Champs EXIF existants remplacés dans le fichier.
+    InPlace,
+    /// Fichier non modifiable sans risque : sidecar XMP à côté du fichier.
+    Sidecar(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+pub struct DateWriteResult {
+    pub path: PathBuf,
+    pub method: Option<DateWriteMethod>,
+    pub previous_date: Option<String>,
+    pub previous_offset: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DateWriteReport {
+    /// Lot du journal (absent en simulation ou si rien n'a été écrit).
+    pub batch_id: Option<String>,
+    pub results: Vec<DateWriteResult>,
+}
+
+// --- Fonction Principale ---
+
+/// Écrit les dates demandées. En `dry_run`, rien n'est modifié : le rapport indique seulement
+/// la méthode qui serait utilisée et les valeurs actuelles. Sans décalage fourni, un
+/// `OffsetTimeOriginal` existant est vidé : il ne correspondrait plus à la nouvelle date.
+/// Chaque écriture est enregistrée dans le journal avant modification du fichier.
+pub fn write_exif_dates(writes: &[DateWrite], dry_run: bool, journal: &Journal) -> DateWriteReport {
+    let batch_id = new_batch_id();
+    let mut written = 0;
+    let mut results = Vec::with_capacity(writes.len());
+
+    for write in writes {
+        let mut result = DateWriteResult { path: write.path.clone(), method: None, previous_date: None, previous_offset: None, error: None };
+        match plan_date_write(write) {
+            Ok(plan) => {
+                result.method = Some(plan.method.clone());
+                result.previous_date = plan.previous_date.clone();
+                result.previous_offset = plan.previous_offset.clone();
+                if !dry_run {
+                    match apply_plan(write, &plan, journal, &batch_id) {
+                        Ok(()) => written += 1,
+                        Err(e) => { eprintln!("Attention : {}", e); result.error = Some(e); }
+                    }
+                }
+            }
+            Err(e) => result.error = Some(format!("Lecture de '{}' impossible: {}", write.path.display(), e)),
+        }
+        results.push(result);
+    }
+
+    DateWriteReport { batch_id: if written > 0 { Some(batch_id) } else { None }, results }
+}
+
+/// Remplace sur place les valeurs ASCII de `DateTimeOriginal` et, si fourni, `OffsetTimeOriginal`.
+/// Utilisé aussi pour restaurer les valeurs d'origine depuis le journal.
+pub fn write_raw_date_fields(path: &Path, date: &str, offset: Option<&str>) -> io::Result<()> {
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let slots = find_date_slots(&mut file)?;
+    let date_slot = slots.date.ok_or_else(|| invalid_data("DateTimeOriginal absent"))?;
+    write_ascii(&mut file, date_slot, date)?;
+    if let Some(offset) = offset {
+        let offset_slot = slots.offset.ok_or_else(|| invalid_data("OffsetTimeOriginal absent"))?;
+        write_ascii(&mut file, offset_slot, offset)?;
+    }
+    file.flush()
+}
+
+// --- Planification ---
+
+struct DatePlan {
+    method: DateWriteMethod,
+    previous_date: Option<String>,
+    previous_offset: Option<String>,
+    previous_sidecar: Option<String>,
+    /// Contenu du sidecar à écrire (méthode `Sidecar`).
+    sidecar_content: Option<String>,
+}
+
+fn plan_date_write(write: &DateWrite) -> io::Result<DatePlan> {
+    let mut file = File::open(&write.path)?;
+    let slots = find_date_slots(&mut file).unwrap_or_default();
+    // Emplacement assez grand pour la valeur et son NUL final
+    let fits = |slot: Option<AsciiSlot>, value_len: u32| slot.is_some_and(|s| s.len > value_len);
+    let in_place = fits(slots.date, DATE_VALUE_LEN) && (write.offset.is_none() || fits(slots.offset, OFFSET_VALUE_LEN));
+
+    if in_place {
+        let previous_date = slots.date.map(|s| read_ascii(&mut file, s)).transpose()?;
+        let previous_offset = slots.offset.map(|s| read_ascii(&mut file, s)).transpose()?;
+        Ok(DatePlan { method: DateWriteMethod::InPlace, previous_date, previous_offset, previous_sidecar: None, sidecar_content: None })
+    } else {
+        let sidecar_path = sidecar_path_for(&write.path);
+        let previous_sidecar = match fs::read_to_string(&sidecar_path) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+        let sidecar_content = match &previous_sidecar {
+            Some(existing) => merge_xmp_date(existing, &xmp_date(write))
+                .map_err(|e| invalid_data(&format!("Sidecar existant '{}' non modifié : {}", sidecar_path.display(), e)))?,
+            None => build_xmp_sidecar(&xmp_date(write)),
+        };
+        Ok(DatePlan { method: DateWriteMethod::Sidecar(sidecar_path), previous_date: None, previous_offset: None, previous_sidecar, sidecar_content: Some(sidecar_content) })
+    }
+}
+
+fn apply_plan(write: &DateWrite, plan: &DatePlan, journal: &Journal, batch_id: &str) -> Result<(), String> {
+    let new_date = write.date.format("%Y:%m:%d %H:%M:%S").to_string();
+    let new_offset = write.offset.map(|o| o.to_string());
+
+    let operation = match &plan.method {
+        DateWriteMethod::InPlace => Operation::ExifDateWrite {
+            path: write.path.clone(),
+            previous_date: plan.previous_date.clone(),
+            previous_offset: plan.previous_offset.clone(),
+            new_date: new_date.clone(),
+            new_offset: new_offset.clone(),
+        },
+        DateWriteMethod::Sidecar(sidecar_path) => Operation::XmpSidecarWrite {
+            path: write.path.clone(),
+            sidecar_path: sidecar_path.clone(),
+            previous_content: plan.previous_sidecar.clone(),
+        },
+    };
+    // Sauvegarde dans le journal avant de toucher au fichier
+    journal
+        .append(batch_id, vec![operation.clone()])
+        .map_err(|e| format!("Écriture du journal impossible: {}", e))?;
+
+    let result = match &plan.method {
+        DateWriteMethod::InPlace => {
+            let offset = new_offset.as_deref().or(plan.previous_offset.as_ref().map(|_| ""));
+            write_raw_date_fields(&write.path, &new_date, offset)
+        }
+        DateWriteMethod::Sidecar(sidecar_path) => fs::write(sidecar_path, plan.sidecar_content.as_deref().unwrap_or_default()),
+    };
+    if let Err(e) = result {
+        let _ = journal.remove_entries(|entry| entry.batch_id == batch_id && entry.operation.path() == &write.path);
+        return Err(format!("Écriture de la date dans '{}' impossible: {}", write.path.display(), e));
+    }
+    Ok(())
+}
+
+// --- Sidecar XMP ---
+
+/// Chemin du sidecar XMP, nom complet conservé (`IMG_001.cr2` -> `IMG_001.cr2.xmp`, convention
+/// de darktable) : `IMG_001.jpg` et `IMG_001.cr2` ont chacun le leur.
+pub fn sidecar_path_for(path: &Path) -> PathBuf {
+    let mut name = path.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    name.push(".xmp");
+    path.with_file_name(name)
+}
+
+/// Date au format XMP (ex: "2023-05-01T10:00:00+02:00").
+fn xmp_date(write: &DateWrite) -> String {
+    let mut date = write.date.format("%Y-%m-%dT%H:%M:%S").to_string();
+    if let Some(offset) = write.offset {
+        date.push_str(&offset.to_string());
+    }
+    date
+}
+
+fn build_xmp_sidecar(date: &str) -> String {
+    format!(
+        "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n\
+         <x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n \
+         <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n  \
+         <rdf:Description rdf:about=\"\"\n    \
+         xmlns:exif=\"{XMP_NS_EXIF}\"\n    \
+         xmlns:photoshop=\"{XMP_NS_PHOTOSHOP}\"\n   \
+         exif:DateTimeOriginal=\"{date}\"\n   \
+         photoshop:DateCreated=\"{date}\"/>\n \
+         </rdf:RDF>\n\
+         </x:xmpmeta>\n\
+         <?xpacket end=\"w\"?>\n"
+    )
+}
+
+/// Place la date dans un sidecar existant (Lightroom, darktable...) sans toucher au reste :
+/// valeur remplacée si la propriété existe (attribut ou élément), sinon ajoutée au premier
+/// `rdf:Description`. Erreur si la structure n'est pas reconnue.
+fn merge_xmp_date(existing: &str, date: &str) -> Result<String, String> {
+    let mut content = existing.to_string();
+    for (prefix, property, namespace) in [("exif", "DateTimeOriginal", XMP_NS_EXIF), ("photoshop", "DateCreated", XMP_NS_PHOTOSHOP)] {
+        let attribute = format!("{}:{}=\"", prefix, property);
+        let element = format!("<{}:{}>", prefix, property);
+        if let Some(start) = content.find(&attribute).map(|i| i + attribute.len()) {
+            let end = content[start..].find('"').map(|i| start + i).ok_or("attribut XMP non terminé")?;
+            content.replace_range(start..end, date);
+        } else if let Some(start) = content.find(&element).map(|i| i + element.len()) {
+            let end = content[start..].find(&format!("</{}:{}>", prefix, property)).map(|i| start + i).ok_or("élément XMP non terminé")?;
+            content.replace_range(start..end, date);
+        } else {
+            let declaration = format!("xmlns:{}=\"", prefix);
+            let mut inserted = format!(" {}{}\"", attribute, date);
+            match content.find(&declaration).map(|i| i + declaration.len()) {
+                Some(start) if !content[start..].starts_with(namespace) => {
+                    return Err(format!("préfixe '{}' associé à un autre espace de noms", prefix));
+                }
+                Some(_) => {}
+                None => inserted = format!(" {}{}\"{}", declaration, namespace, inserted),
+            }
+            let tag = "<rdf:Description";
+            let position = content.find(tag).map(|i| i + tag.len()).ok_or("aucun rdf:Description")?;
+            content.insert_str(position, &inserted);
+        }
+    }
+    Ok(content)
+}
+
+// --- Lecture de la structure TIFF ---
+
+#[derive(Debug, Clone, Copy)]
+struct AsciiSlot {
+    position: u64,
+    len: u32,
+}
+
+#[derive(Debug, Default)]
+struct DateSlots {
+    date: Option<AsciiSlot>,
+    offset: Option<AsciiSlot>,
+}
+
+struct TiffReader<'a> {
+    file: &'a mut File,
+    base: u64,
+    little_endian: bool,
+}
+
+impl TiffReader<'_> {
+    fn bytes<const N: usize>(&mut self, offset: u64) -> io::Result<[u8; N]> {
+        let mut buf = [0u8; N];
+        self.file.seek(SeekFrom::Start(self.base + offset))?;
+        self.file.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn u16_at(&mut self, offset: u64) -> io::Result<u16> {
+        let b = self.bytes::<2>(offset)?;
+        Ok(if self.little_endian { u16::from_le_bytes(b) } else { u16::from_be_bytes(b) })
+    }
+
+    fn u32_at(&mut self, offset: u64) -> io::Result<u32> {
+        let b = self.bytes::<4>(offset)?;
+        Ok(if self.little_endian { u32::from_le_bytes(b) } else { u32::from_be_bytes(b) })
+    }
+
+    /// Parcourt les entrées d'un IFD : (tag, type, count, position relative de la valeur).
+    fn entries(&mut self, ifd_offset: u64) -> io::Result<Vec<(u16, u16, u32, u64)>> {
+        let count = self.u16_at(ifd_offset)?;
+        if count > MAX_IFD_ENTRIES {
+            return Err(invalid_data("IFD corrompu"));
+        }
+        let mut entries = Vec::with_capacity(count as usize);
+        for i in 0..count as u64 {
+            let entry_offset = ifd_offset + 2 + i * 12;
+            let tag = self.u16_at(entry_offset)?;
+            let field_type = self.u16_at(entry_offset + 2)?;
+            let value_count = self.u32_at(entry_offset + 4)?;
+            // Valeurs ASCII de 4 octets ou moins : stockées dans l'entrée elle-même
+            let value_position = if field_type == TYPE_ASCII && value_count <= 4 {
+                entry_offset + 8
+            } else {
+                self.u32_at(entry_offset + 8)? as u64
+            };
+            entries.push((tag, field_type, value_count, value_position));
+        }
+        Ok(entries)
+    }
+}
+
+fn find_date_slots(file: &mut File) -> io::Result<DateSlots> {
+    let base = locate_tiff_header(file)?.ok_or_else(|| invalid_data("Pas de bloc EXIF modifiable"))?;
+    let mut header = [0u8; 2];
+    file.seek(SeekFrom::Start(base))?;
+    file.read_exact(&mut header)?;
+    let little_endian = match &header {
+        b"II" => true,
+        b"MM" => false,
+        _ => return Err(invalid_data("En-tête TIFF invalide")),
+    };
+    let mut tiff = TiffReader { file, base, little_endian };
+
+    let ifd0_offset = tiff.u32_at(4)? as u64;
+    let exif_ifd_offset = tiff
+        .entries(ifd0_offset)?
+        .into_iter()
+        .find(|(tag, ..)| *tag == TAG_EXIF_IFD_POINTER)
+        .map(|(_, _, _, position)| position);
+
+    let mut slots = DateSlots::default();
+    if let Some(exif_ifd_offset) = exif_ifd_offset {
+        for (tag, field_type, count, position) in tiff.entries(exif_ifd_offset)? {
+            if field_type != TYPE_ASCII {
+                continue;
+            }
+            let slot = AsciiSlot { position: base + position, len: count };
+            match tag {
+                TAG_DATE_TIME_ORIGINAL => slots.date = Some(slot),
+                TAG_OFFSET_TIME_ORIGINAL => slots.offset = Some(slot),
+                _ => {}
+            }
+        }
+    }
+    Ok(slots)
+}
+
+/// Position absolue de l'en-tête TIFF : début du fichier pour un TIFF,
+/// ou contenu du segment APP1 "Exif" pour un JPEG. `None` pour les autres formats.
+fn locate_tiff_header(file: &mut File) -> io::Result<Option<u64>> {
+    let mut magic = [0u8; 4];
+    file.seek(SeekFrom::Start(0))?;
+    if file.read_exact(&mut magic).is_err() {
+        return Ok(None);
+    }
+    if &magic == b"II*\0" || &magic == b"MM\0*" {
+        return Ok(Some(0));
+    }
+    if magic[0] != 0xFF || magic[1] != 0xD8 {
+        return Ok(None);
+    }
+
+    let mut position = 2u64;
+    loop {
+        let mut marker = [0u8; 4];
+        file.seek(SeekFrom::Start(position))?;
+        if file.read_exact(&mut marker).is_err() || marker[0] != 0xFF {
+            return Ok(None);
+        }
+        // Début des données image (SOS) ou fin d'image (EOI) : plus d'EXIF à chercher
+        if marker[1] == 0xDA || marker[1] == 0xD9 {
+            return Ok(None);
+        }
+        let segment_len = u16::from_be_bytes([marker[2], marker[3]]) as u64;
+        if marker[1] == 0xE1 && segment_len >= 8 {
+            let mut signature = [0u8; 6];
+            file.read_exact(&mut signature)?;
+            if &signature == b"Exif\0\0" {
+                return Ok(Some(position + 10));
+            }
+        }
+        position += 2 + segment_len;
+    }
+}
+
+fn read_ascii(file: &mut File, slot: AsciiSlot) -> io::Result<String> {
+    let mut buf = vec![0u8; slot.len as usize];
+    file.seek(SeekFrom::Start(slot.position))?;
+    file.read_exact(&mut buf)?;
+    Ok(String::from_utf8_lossy(&buf).trim_end_matches('\0').trim().to_string())
+}
+
+fn write_ascii(file: &mut File, slot: AsciiSlot, value: &str) -> io::Result<()> {
+    // Le compteur ASCII inclut le NUL final : la valeur doit tenir strictement dans l'emplacement
+    if value.len() >= slot.len as usize {
+        return Err(invalid_data("Valeur trop longue pour le champ EXIF existant"));
+    }
+    let mut buf = vec![0u8; slot.len as usize];
+    buf[..value.len()].copy_from_slice(value.as_bytes());
+    file.seek(SeekFrom::Start(slot.position))?;
+    file.write_all(&buf)
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_dir;
+    use chrono::NaiveDate;
+
+    /// TIFF minimal (petit-boutiste) : IFD0 -> IFD EXIF avec DateTimeOriginal et OffsetTimeOriginal
+    /// de `date_len` et `offset_len` octets (NUL compris).
+    fn tiff_bytes(date_value: &str, date_len: u32, offset_value: &str, offset_len: u32) -> Vec<u8> {
+        let mut tiff = b"II*\0".to_vec();
+        tiff.extend(8u32.to_le_bytes());
+        // IFD0 (offset 8) : une entrée, pointeur vers l'IFD EXIF en 26
+        tiff.extend(1u16.to_le_bytes());
+        tiff.extend(TAG_EXIF_IFD_POINTER.to_le_bytes()); tiff.extend(4u16.to_le_bytes()); tiff.extend(1u32.to_le_bytes()); tiff.extend(26u32.to_le_bytes());
+        tiff.extend(0u32.to_le_bytes());
+        // IFD EXIF (offset 26) : deux entrées ASCII, valeurs à partir de 56
+        let date_position = 56u32;
+        let offset_position = date_position + date_len;
+        tiff.extend(2u16.to_le_bytes());
+        for (tag, len, position) in [(TAG_DATE_TIME_ORIGINAL, date_len, date_position), (TAG_OFFSET_TIME_ORIGINAL, offset_len, offset_position)] {
+            tiff.extend(tag.to_le_bytes()); tiff.extend(TYPE_ASCII.to_le_bytes()); tiff.extend(len.to_le_bytes()); tiff.extend(position.to_le_bytes());
+        }
+        tiff.extend(0u32.to_le_bytes());
+        assert_eq!(tiff.len(), 56);
+        for (value, len) in [(date_value, date_len), (offset_value, offset_len)] {
+            let mut field = value.as_bytes().to_vec();
+            field.resize(len as usize, 0);
+            tiff.extend(field);
+        }
+        tiff
+    }
+
+    fn jpeg_bytes(tiff: &[u8]) -> Vec<u8> {
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend(((tiff.len() + 8) as u16).to_be_bytes());
+        jpeg.extend(b"Exif\0\0");
+        jpeg.extend(tiff);
+        jpeg.extend([0xFF, 0xD9]);
+        jpeg
+    }
+
+    fn date_write(path: &Path) -> DateWrite {
+        DateWrite {
+            path: path.to_path_buf(),
+            date: NaiveDate::from_ymd_opt(2023, 5, 1).unwrap().and_hms_opt(10, 20, 30).unwrap(),
+            offset: Some(FixedOffset::east_opt(2 * 3600).unwrap()),
+        }
+    }
+
+    fn read_fields(path: &Path) -> (String, String) {
+        let mut file = File::open(path).unwrap();
+        let slots = find_date_slots(&mut file).unwrap();
+        (read_ascii(&mut file, slots.date.unwrap()).unwrap(), read_ascii(&mut file, slots.offset.unwrap()).unwrap())
+    }
+
+    #[test]
+    fn rewrites_fields_in_place_when_they_fit() {
+        let dir = test_dir("writer_fits");
+        for (name, bytes) in [("a.jpg", jpeg_bytes(&tiff_bytes("2000:01:01 00:00:00", 20, "+00:00", 7))), ("a.tif", tiff_bytes("2000:01:01 00:00:00", 20, "+00:00", 7))] {
+            let path = dir.join(name);
+            fs::write(&path, &bytes).unwrap();
+            let journal = Journal::new(dir.join("journal.jsonl"));
+            let report = write_exif_dates(&[date_write(&path)], false, &journal);
+            let result = &report.results[0];
+            assert_eq!(result.error, None);
+            assert_eq!(result.method, Some(DateWriteMethod::InPlace));
+            assert_eq!(result.previous_date.as_deref(), Some("2000:01:01 00:00:00"));
+            assert_eq!(read_fields(&path), ("2023:05:01 10:20:30".to_string(), "+02:00".to_string()));
+            assert_eq!(fs::read(&path).unwrap().len(), bytes.len());
+            assert!(!sidecar_path_for(&path).exists());
+        }
+    }
+
+    #[test]
+    fn clears_stale_offset_without_new_offset_and_undo_restores_it() {
+        let dir = test_dir("writer_clear_offset");
+        let path = dir.join("e.jpg");
+        let bytes = jpeg_bytes(&tiff_bytes("2000:01:01 00:00:00", 20, "+05:00", 7));
+        fs::write(&path, &bytes).unwrap();
+        let journal = Journal::new(dir.join("journal.jsonl"));
+        let report = write_exif_dates(&[DateWrite { offset: None, ..date_write(&path) }], false, &journal);
+        assert_eq!(report.results[0].error, None);
+        assert_eq!(report.results[0].previous_offset.as_deref(), Some("+05:00"));
+        assert_eq!(read_fields(&path), ("2023:05:01 10:20:30".to_string(), String::new()));
+
+        for entry in journal.read_all().unwrap() {
+            crate::journal::undo::undo_operation(&entry.operation).unwrap();
+        }
+        assert_eq!(fs::read(&path).unwrap(), bytes);
+    }
+
+    #[test]
+    fn falls_back_to_sidecar_when_slot_is_too_short() {
+        let dir = test_dir("writer_short");
+        let path = dir.join("b.jpg");
+        let bytes = jpeg_bytes(&tiff_bytes("2000:01:01", 11, "+00:00", 7));
+        fs::write(&path, &bytes).unwrap();
+        let journal = Journal::new(dir.join("journal.jsonl"));
+        let report = write_exif_dates(&[date_write(&path)], false, &journal);
+        let sidecar = dir.join("b.jpg.xmp");
+        assert_eq!(report.results[0].error, None);
+        assert_eq!(report.results[0].method, Some(DateWriteMethod::Sidecar(sidecar.clone())));
+        assert_eq!(fs::read(&path).unwrap(), bytes);
+        assert!(fs::read_to_string(&sidecar).unwrap().contains("exif:DateTimeOriginal=\"2023-05-01T10:20:30+02:00\""));
+    }
+
+    #[test]
+    fn dry_run_leaves_bytes_untouched() {
+        let dir = test_dir("writer_dry_run");
+        let fits = dir.join("c.jpg");
+        let short = dir.join("d.jpg");
+        let fits_bytes = jpeg_bytes(&tiff_bytes("2000:01:01 00:00:00", 20, "+00:00", 7));
+        let short_bytes = jpeg_bytes(&tiff_bytes("2000", 5, "+00:00", 7));
+        fs::write(&fits, &fits_bytes).unwrap();
+        fs::write(&short, &short_bytes).unwrap();
+        let journal = Journal::new(dir.join("journal.jsonl"));
+        let report = write_exif_dates(&[date_write(&fits), date_write(&short)], true, &journal);
+        assert_eq!(report.batch_id, None);
+        assert_eq!(report.results[0].method, Some(DateWriteMethod::InPlace));
+        assert!(matches!(report.results[1].method, Some(DateWriteMethod::Sidecar(_))));
+        assert_eq!(fs::read(&fits).unwrap(), fits_bytes);
+        assert_eq!(fs::read(&short).unwrap(), short_bytes);
+        assert!(!sidecar_path_for(&short).exists());
+        assert!(!journal.path().exists());
+    }
+
+    #[test]
+    fn sidecar_names_keep_the_extension() {
+        assert_eq!(sidecar_path_for(Path::new("/photos/IMG.cr2")), PathBuf::from("/photos/IMG.cr2.xmp"));
+        assert_ne!(sidecar_path_for(Path::new("IMG.jpg")), sidecar_path_for(Path::new("IMG.cr2")));
+    }
+
+    #[test]
+    fn merges_date_into_existing_sidecar() {
+        let existing = "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"><rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\
+            <rdf:Description rdf:about=\"\" xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\" xmlns:exif=\"http://ns.adobe.com/exif/1.0/\" xmp:Rating=\"4\" exif:DateTimeOriginal=\"2000-01-01T00:00:00\">\
+            <dc:subject><rdf:Bag><rdf:li>vacances</rdf:li></rdf:Bag></dc:subject></rdf:Description></rdf:RDF></x:xmpmeta>";
+        let merged = merge_xmp_date(existing, "2023-05-01T10:20:30").unwrap();
+        assert!(merged.contains("exif:DateTimeOriginal=\"2023-05-01T10:20:30\""));
+        assert!(merged.contains("photoshop:DateCreated=\"2023-05-01T10:20:30\""));
+        assert!(merged.contains(&format!("xmlns:photoshop=\"{}\"", XMP_NS_PHOTOSHOP)));
+        assert!(merged.contains("xmp:Rating=\"4\"") && merged.contains("<rdf:li>vacances</rdf:li>"));
+        assert!(!merged.contains("2000-01-01"));
+
+        let element_form = "<rdf:Description xmlns:exif=\"http://ns.adobe.com/exif/1.0/\"><exif:DateTimeOriginal>2000-01-01T00:00:00</exif:DateTimeOriginal></rdf:Description>";
+        assert!(merge_xmp_date(element_form, "2023-05-01T10:20:30").unwrap().contains("<exif:DateTimeOriginal>2023-05-01T10:20:30</exif:DateTimeOriginal>"));
+        assert!(merge_xmp_date("<x:xmpmeta/>", "2023-05-01T10:20:30").is_err());
+    }
+}
@@ -0,0 +1,295 @@
+// timeshot_core/src/export/checksums.rs
+//
+// Fichiers de sommes de contrôle au format `sha256sum` / `b3sum` ("<hash>  <chemin>"),
+// en écriture (à partir des analyses) et en lecture (pour vérification).
+
+use crate::hash::compute::{compute_file_hash_with, HashAlgorithm};
+use crate::hash::manifest::{verify_manifest, Manifest, ManifestEntry, VerificationReport};
+use crate::types::FileAnalysis;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Emplacement des fichiers de sommes de contrôle.
+#[derive(Debug, Clone)]
+pub enum ChecksumLayout {
+    /// Un seul fichier ; les chemins sont relatifs à son dossier.
+    Global(PathBuf),
+    /// Un fichier (`checksum_file_name`) dans chaque dossier contenant des fichiers analysés.
+    PerDirectory,
+}
+
+#[derive(Debug, Default)]
+pub struct ChecksumExportReport {
+    pub written_files: Vec<PathBuf>,
+    pub entries: usize,
+    pub errors: Vec<String>,
+}
+
+/// Nom conventionnel du fichier de sommes ("B3SUMS", "SHA256SUMS", "XXH3SUMS").
+pub fn checksum_file_name(algorithm: HashAlgorithm) -> &'static str {
+    match algorithm {
+        HashAlgorithm::Blake3 => "B3SUMS",
+        HashAlgorithm::Sha256 => "SHA256SUMS",
+        HashAlgorithm::Xxh3 => "XXH3SUMS",
+    }
+}
+
+/// Fichier de sommes d'après son nom conventionnel.
+pub fn is_checksum_file(path: &Path) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    HashAlgorithm::ALL.iter().any(|&a| checksum_file_name(a) == name)
+}
+
+/// Écrit les sommes de contrôle des fichiers analysés (hors fichiers de sommes existants). Le hash de l'analyse est réutilisé
+/// s'il a été calculé avec le même algorithme, sinon le fichier est haché.
+pub fn export_checksums(files: &[FileAnalysis], algorithm: HashAlgorithm, layout: &ChecksumLayout) -> ChecksumExportReport {
+    let mut report = ChecksumExportReport::default();
+    // Fichier de sommes -> (dossier de référence, entrées)
+    let mut outputs: BTreeMap<PathBuf, (PathBuf, Vec<ManifestEntry>)> = BTreeMap::new();
+
+    for file in files {
+        let path = &file.full_original_path;
+        if is_checksum_file(path) { continue; }
+        let hash = match &file.file_hash {
+            Some(hash) if file.hash_algorithm == algorithm => hash.clone(),
+            _ => match compute_file_hash_with(path, algorithm) {
+                Ok(hash) => hash,
+                Err(e) => { report.errors.push(format!("Hash impossible pour '{}': {}", path.display(), e)); continue; }
+            },
+        };
+        let (output, base_dir) = match layout {
+            ChecksumLayout::Global(output) => (output.clone(), output.parent().map(Path::to_path_buf).unwrap_or_default()),
+            ChecksumLayout::PerDirectory => {
+                let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+                (dir.join(checksum_file_name(algorithm)), dir)
+            }
+        };
+        // Hors du dossier de référence : chemin absolu (accepté par sha256sum -c)
+        let relative = path.strip_prefix(&base_dir).map(Path::to_path_buf).unwrap_or_else(|_| path.clone());
+        outputs.entry(output).or_insert_with(|| (base_dir, Vec::new())).1.push(ManifestEntry { path: relative, hash });
+    }
+
+    for (output, (_, mut entries)) in outputs {
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        let count = entries.len();
+        match write_checksum_file(&output, &Manifest { algorithm, entries }) {
+            Ok(()) => { report.entries += count; report.written_files.push(output); }
+            Err(e) => report.errors.push(format!("Écriture de '{}' impossible: {}", output.display(), e)),
+        }
+    }
+    report
+}
+
+/// Écrit un manifeste au format texte "<hash>  <chemin>" (séparateurs `/`).
+pub fn write_checksum_file(path: &Path, manifest: &Manifest) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() { fs::create_dir_all(parent)?; }
+    }
+    let mut writer = BufWriter::new(File::create(path)?);
+    for entry in &manifest.entries {
+        // Séparateurs Windows convertis ; ailleurs '\\' est un caractère du nom
+        let name = entry.path.to_string_lossy();
+        let name = if cfg!(windows) { name.replace('\\', "/") } else { name.into_owned() };
+        // Convention GNU : une ligne commençant par '\' a un nom échappé
+        if name.contains('\n') || name.contains('\\') {
+            writeln!(writer, "\\{}  {}", entry.hash, name.replace('\\', "\\\\").replace('\n', "\\n"))?;
+        } else {
+            writeln!(writer, "{}  {}", entry.hash, name)?;
+        }
+    }
+    writer.flush()
+}
+
+/// Lit un fichier de sommes (`sha256sum`, `b3sum`, avec ou sans marqueur binaire `*`).
+/// Sans algorithme fourni, il est déduit du nom du fichier puis de la longueur des hashes.
+pub fn read_checksum_file(path: &Path, algorithm: Option<HashAlgorithm>) -> io::Result<Manifest> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut entries = Vec::new();
+    for (line_number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() || line.starts_with('#') { continue; }
+        let entry = parse_checksum_line(line).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("Ligne {} invalide : {}", line_number + 1, line))
+        })?;
+        entries.push(entry);
+    }
+
+    let algorithm = match algorithm.or_else(|| algorithm_from_file_name(path)) {
+        Some(algorithm) => algorithm,
+        None => algorithm_from_hash_len(&entries).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "Algorithme de hash impossible à déterminer")
+        })?,
+    };
+    if let Some(entry) = entries.iter().find(|e| e.hash.len() != algorithm.hex_len()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Hash {} invalide pour '{}'", algorithm, entry.path.display()),
+        ));
+    }
+    Ok(Manifest { algorithm, entries })
+}
+
+/// Vérifie le dossier contenant un fichier de sommes. Ce fichier et les autres fichiers de sommes
+/// (noms conventionnels) ne sont pas signalés comme nouveaux.
+pub fn verify_checksum_file(path: &Path, algorithm: Option<HashAlgorithm>, recursive: bool) -> io::Result<VerificationReport> {
+    let manifest = read_checksum_file(path, algorithm)?;
+    let root = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let mut report = verify_manifest(&manifest, root, recursive, &[path.to_path_buf()]);
+    report.new_files.retain(|p| !is_checksum_file(p));
+    Ok(report)
+}
+
+fn parse_checksum_line(line: &str) -> Option<ManifestEntry> {
+    let (escaped, line) = match line.strip_prefix('\\') { Some(rest) => (true, rest), None => (false, line) };
+    let (hash, name) = line.split_once(' ')?;
+    // Deux espaces (mode texte) ou " *" (mode binaire)
+    let name = name.strip_prefix(' ').or_else(|| name.strip_prefix('*'))?;
+    if hash.is_empty() || name.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) { return None; }
+    let name = if escaped { unescape_name(name) } else { name.to_string() };
+    Some(ManifestEntry { path: PathBuf::from(name), hash: hash.to_lowercase() })
+}
+
+fn unescape_name(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' { result.push(c); continue; }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+fn algorithm_from_file_name(path: &Path) -> Option<HashAlgorithm> {
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    if name.contains("sha256") {
+        Some(HashAlgorithm::Sha256)
+    } else if name.contains("b3") || name.contains("blake3") {
+        Some(HashAlgorithm::Blake3)
+    } else if name.contains("xxh3") {
+        Some(HashAlgorithm::Xxh3)
+    } else {
+        None
+    }
+}
+
+/// 16 caractères : xxh3 ; 64 : ambigu (BLAKE3 ou SHA-256), BLAKE3 par défaut.
+fn algorithm_from_hash_len(entries: &[ManifestEntry]) -> Option<HashAlgorithm> {
+    match entries.first()?.hash.len() {
+        16 => Some(HashAlgorithm::Xxh3),
+        64 => Some(HashAlgorithm::Blake3),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{file_analysis, test_dir};
+
+    fn entry(path: &str, hash: &str) -> ManifestEntry {
+        ManifestEntry { path: PathBuf::from(path), hash: hash.to_string() }
+    }
+
+    #[test]
+    fn round_trips_names_with_newlines_and_backslashes() {
+        let dir = test_dir("checksums_round_trip");
+        let hash = "ab".repeat(32);
+        let mut entries = vec![entry("a.jpg", &hash), entry("sous dossier/b c.jpg", &hash), entry("ligne\nsuite.jpg", &hash)];
+        if cfg!(unix) { entries.push(entry("barre\\oblique.jpg", &hash)); }
+        let manifest = Manifest { algorithm: HashAlgorithm::Sha256, entries };
+        let path = dir.join("SHA256SUMS");
+        write_checksum_file(&path, &manifest).unwrap();
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.contains(&format!("\\{}  ligne\\nsuite.jpg\n", hash)));
+        let read = read_checksum_file(&path, None).unwrap();
+        assert_eq!(read.algorithm, HashAlgorithm::Sha256);
+        assert_eq!(read.entries, manifest.entries);
+    }
+
+    #[test]
+    fn reads_binary_mode_markers_and_rejects_bad_lines() {
+        let dir = test_dir("checksums_binary");
+        let hash = "0123456789abcdef";
+        let path = dir.join("sums.txt");
+        fs::write(&path, format!("# commentaire\n{h} *binaire.raw\r\n{h}  texte.txt\n\n{H}  majuscules.jpg\n", h = hash, H = hash.to_uppercase())).unwrap();
+        let manifest = read_checksum_file(&path, None).unwrap();
+        assert_eq!(manifest.algorithm, HashAlgorithm::Xxh3);
+        assert_eq!(manifest.entries, vec![entry("binaire.raw", hash), entry("texte.txt", hash), entry("majuscules.jpg", hash)]);
+
+        // Un seul espace, ou hash non hexadécimal : ligne invalide
+        for line in [format!("{} nom.jpg", hash), format!("{}  nom.jpg", "z".repeat(16))] {
+            fs::write(&path, line).unwrap();
+            assert_eq!(read_checksum_file(&path, None).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn detects_algorithm_from_file_name_then_hash_length() {
+        let dir = test_dir("checksums_detect");
+        let blake3_len = "a".repeat(64);
+        for (name, hash, expected) in [
+            ("B3SUMS", &blake3_len, HashAlgorithm::Blake3),
+            ("photos.blake3", &blake3_len, HashAlgorithm::Blake3),
+            ("SHA256SUMS", &blake3_len, HashAlgorithm::Sha256),
+            ("photos.sha256", &blake3_len, HashAlgorithm::Sha256),
+            ("XXH3SUMS", &"a".repeat(16), HashAlgorithm::Xxh3),
+            // Nom sans indice : 64 caractères -> BLAKE3, 16 -> XXH3
+            ("sommes.txt", &blake3_len, HashAlgorithm::Blake3),
+            ("liste.txt", &"a".repeat(16), HashAlgorithm::Xxh3),
+        ] {
+            let path = dir.join(name);
+            fs::write(&path, format!("{}  a.jpg\n", hash)).unwrap();
+            assert_eq!(read_checksum_file(&path, None).unwrap().algorithm, expected, "{}", name);
+        }
+        // Algorithme fourni prioritaire, longueur vérifiée
+        let path = dir.join("B3SUMS");
+        assert_eq!(read_checksum_file(&path, Some(HashAlgorithm::Sha256)).unwrap().algorithm, HashAlgorithm::Sha256);
+        assert!(read_checksum_file(&path, Some(HashAlgorithm::Xxh3)).is_err());
+        let path = dir.join("inconnu.txt");
+        fs::write(&path, format!("{}  a.jpg\n", "a".repeat(40))).unwrap();
+        assert!(read_checksum_file(&path, None).is_err());
+    }
+
+    #[test]
+    fn exports_global_and_per_directory_layouts() {
+        let dir = test_dir("checksums_layouts");
+        fs::create_dir_all(dir.join("a")).unwrap();
+        fs::create_dir_all(dir.join("b")).unwrap();
+        let paths = [dir.join("a/1.jpg"), dir.join("a/2.jpg"), dir.join("b/3.jpg")];
+        for (i, path) in paths.iter().enumerate() { fs::write(path, format!("contenu {}", i)).unwrap(); }
+        let mut files: Vec<FileAnalysis> = paths.iter().map(file_analysis).collect();
+        // Hash d'un autre algorithme : recalculé
+        files[0].file_hash = Some("0".repeat(64));
+        files[0].hash_algorithm = HashAlgorithm::Sha256;
+        let expected_hash = |path: &Path| compute_file_hash_with(path, HashAlgorithm::Blake3).unwrap();
+
+        let global = dir.join("B3SUMS");
+        let report = export_checksums(&files, HashAlgorithm::Blake3, &ChecksumLayout::Global(global.clone()));
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!((report.entries, report.written_files), (3, vec![global.clone()]));
+        let manifest = read_checksum_file(&global, None).unwrap();
+        let expected: Vec<ManifestEntry> = ["a/1.jpg", "a/2.jpg", "b/3.jpg"].iter().zip(&paths).map(|(name, path)| entry(name, &expected_hash(path))).collect();
+        assert_eq!(manifest.entries, expected);
+        let verification = verify_checksum_file(&global, None, true).unwrap();
+        assert_eq!(verification.verified, 3);
+        assert!(verification.changed.is_empty() && verification.missing.is_empty() && verification.new_files.is_empty());
+
+        // Le fichier de sommes global fait partie des fichiers analysés : ignoré
+        files.push(file_analysis(&global));
+        let report = export_checksums(&files, HashAlgorithm::Blake3, &ChecksumLayout::PerDirectory);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.entries, 3);
+        assert_eq!(report.written_files, vec![dir.join("a/B3SUMS"), dir.join("b/B3SUMS")]);
+        let manifest = read_checksum_file(&dir.join("b/B3SUMS"), None).unwrap();
+        assert_eq!(manifest.entries, vec![entry("3.jpg", &expected_hash(&paths[2]))]);
+        assert_eq!(verify_checksum_file(&dir.join("a/B3SUMS"), None, false).unwrap().verified, 2);
+    }
+}
@@ -1,35 +1,161 @@
-
 use crate::types::FileAnalysis;
+use chrono::NaiveDateTime;
 use std::fs::File;
-use csv::Writer;
+use csv::WriterBuilder;
+
+/// Colonnes disponibles pour l'export CSV.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvColumn {
+    OriginalName,
+    FolderName,
+    FullPath,
+    FileSize,
+    DateTaken,
+    CreateDate,
+    ModifyDate,
+    /// Origine de la meilleure date (`FileAnalysis::best_date`).
+    DateSource,
+    Artist,
+    Title,
+    Description,
+    Keywords,
+    CameraModel,
+    LensModel,
+    FileHash,
+    HashAlgorithm,
+    IsDuplicate,
+    IsProbableDuplicate,
+    NewName,
+    /// Tag EXIF de `other_fields`, par son nom (ex: "ExposureTime") ou sa clé complète ("ExposureTime (IFD0)").
+    ExifTag(String),
+}
+
+impl CsvColumn {
+    /// Colonnes de l'export historique, dans leur ordre.
+    pub fn default_columns() -> Vec<CsvColumn> {
+        use CsvColumn::*;
+        vec![
+            OriginalName, FolderName, DateTaken, CreateDate, ModifyDate,
+            Artist, Title, Description, Keywords, CameraModel,
+            LensModel, FileHash, IsDuplicate, NewName,
+        ]
+    }
+
+    /// Nom de la colonne dans l'en-tête ; les tags EXIF sont préfixés par "exif:".
+    pub fn header(&self) -> String {
+        use CsvColumn::*;
+        let name = match self {
+            OriginalName => "original_name",
+            FolderName => "folder_name",
+            FullPath => "full_path",
+            FileSize => "file_size",
+            DateTaken => "date_taken",
+            CreateDate => "create_date",
+            ModifyDate => "modify_date",
+            DateSource => "date_source",
+            Artist => "artist",
+            Title => "title",
+            Description => "description",
+            Keywords => "keywords",
+            CameraModel => "camera_model",
+            LensModel => "lens_model",
+            FileHash => "file_hash",
+            HashAlgorithm => "hash_algorithm",
+            IsDuplicate => "is_duplicate",
+            IsProbableDuplicate => "is_probable_duplicate",
+            NewName => "new_name",
+            ExifTag(tag) => return format!("exif:{}", tag),
+        };
+        name.to_string()
+    }
+
+    /// Inverse de `header` (ex: pour une liste de colonnes saisie par l'utilisateur).
+    pub fn from_header(header: &str) -> Option<CsvColumn> {
+        let header = header.trim();
+        if let Some(tag) = header.strip_prefix("exif:") {
+            return (!tag.is_empty()).then(|| CsvColumn::ExifTag(tag.to_string()));
+        }
+        CsvColumn::all_fixed().into_iter().find(|c| c.header() == header)
+    }
+
+    /// Toutes les colonnes hors tags EXIF.
+    pub fn all_fixed() -> Vec<CsvColumn> {
+        use CsvColumn::*;
+        vec![
+            OriginalName, FolderName, FullPath, FileSize, DateTaken, CreateDate, ModifyDate, DateSource,
+            Artist, Title, Description, Keywords, CameraModel, LensModel,
+            FileHash, HashAlgorithm, IsDuplicate, IsProbableDuplicate, NewName,
+        ]
+    }
+
+    fn value(&self, f: &FileAnalysis, date_format: &str) -> String {
+        use CsvColumn::*;
+        let date = |d: Option<NaiveDateTime>| d.map(|d| d.format(date_format).to_string()).unwrap_or_default();
+        let text = |t: &Option<String>| t.clone().unwrap_or_default();
+        match self {
+            OriginalName => f.original_name.clone(),
+            FolderName => f.folder_name.clone(),
+            FullPath => f.full_original_path.to_string_lossy().into_owned(),
+            FileSize => f.file_size.to_string(),
+            DateTaken => date(f.exif.date_taken),
+            CreateDate => date(f.exif.create_date),
+            ModifyDate => date(f.exif.modify_date),
+            DateSource => f.best_date().1.name().to_string(),
+            Artist => text(&f.exif.artist),
+            Title => text(&f.exif.title),
+            Description => text(&f.exif.description),
+            Keywords => f.exif.keywords.join(","),
+            CameraModel => text(&f.exif.camera_model),
+            LensModel => text(&f.exif.lens_model),
+            FileHash => text(&f.file_hash),
+            HashAlgorithm => if f.file_hash.is_some() { f.hash_algorithm.name().to_string() } else { String::new() },
+            IsDuplicate => f.is_duplicate.to_string(),
+            IsProbableDuplicate => f.is_probable_duplicate.to_string(),
+            NewName => text(&f.new_name),
+            ExifTag(tag) => exif_tag_value(f, tag).unwrap_or_default(),
+        }
+    }
+}
+
+/// Valeur d'un tag de `other_fields` : clé exacte, sinon première clé "<tag> (IFDn)".
+fn exif_tag_value(f: &FileAnalysis, tag: &str) -> Option<String> {
+    if let Some(value) = f.exif.other_fields.get(tag) {
+        return Some(value.clone());
+    }
+    let prefix = format!("{} (", tag);
+    let mut keys: Vec<&String> = f.exif.other_fields.keys().filter(|k| k.starts_with(&prefix)).collect();
+    keys.sort();
+    keys.first().map(|k| f.exif.other_fields[*k].clone())
+}
+
+/// Options de l'export CSV.
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    pub columns: Vec<CsvColumn>,
+    /// Séparateur (`b';'` pour les tableurs en français).
+    pub delimiter: u8,
+    /// Format chrono des dates (ex: "%d/%m/%Y %H:%M:%S").
+    pub date_format: String,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions { columns: CsvColumn::default_columns(), delimiter: b',', date_format: "%Y-%m-%d %H:%M:%S".to_string() }
+    }
+}
 
 pub fn export_to_csv(path: &str, data: &[FileAnalysis]) -> csv::Result<()> {
+    export_to_csv_with_options(path, data, &CsvOptions::default())
+}
+
+pub fn export_to_csv_with_options(path: &str, data: &[FileAnalysis], options: &CsvOptions) -> csv::Result<()> {
     let file = File::create(path)?;
-    let mut writer = Writer::from_writer(file);
+    let mut writer = WriterBuilder::new().delimiter(options.delimiter).from_writer(file);
 
-    writer.write_record(&[
-        "original_name", "folder_name", "date_taken", "create_date", "modify_date",
-        "artist", "title", "description", "keywords", "camera_model",
-        "lens_model", "file_hash", "is_duplicate", "new_name"
-    ])?;
+    writer.write_record(options.columns.iter().map(CsvColumn::header))?;
 
     for f in data {
-        writer.write_record(&[
-            &f.original_name,
-            &f.folder_name,
-            &f.exif.date_taken.map(|d| d.to_string()).unwrap_or_default(),
-            &f.exif.create_date.map(|d| d.to_string()).unwrap_or_default(),
-            &f.exif.modify_date.map(|d| d.to_string()).unwrap_or_default(),
-            f.exif.artist.as_deref().unwrap_or(""),
-            f.exif.title.as_deref().unwrap_or(""),
-            f.exif.description.as_deref().unwrap_or(""),
-            &f.exif.keywords.join(","),
-            f.exif.camera_model.as_deref().unwrap_or(""),
-            f.exif.lens_model.as_deref().unwrap_or(""),
-            f.file_hash.as_deref().unwrap_or(""),
-            &f.is_duplicate.to_string(),
-            f.new_name.as_deref().unwrap_or(""),
-        ])?;
+        writer.write_record(options.columns.iter().map(|c| c.value(f, &options.date_format)))?;
     }
 
     writer.flush()?;
@@ -0,0 +1,197 @@
+// timeshot_core/src/export/html.rs
+//
+// Rapport HTML autonome (style et tri intégrés) : statistiques, doublons, fichiers sans date et plan de renommage.
+
+use crate::hash::groups::{find_duplicate_groups, KeeperStrategy};
+use crate::types::{format_size, FileAnalysis};
+use chrono::Local;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em;color:#222}h1{margin-bottom:0}.meta{color:#777}\
+table{border-collapse:collapse;margin:1em 0;width:100%}th,td{border:1px solid #ccc;padding:4px 8px;text-align:left}\
+th{background:#eee;cursor:pointer;user-select:none}th:after{content:' \\2195';color:#aaa}\
+tr:nth-child(even) td{background:#fafafa}.dup{color:#d35400}.probable{color:#b8860b}.keeper{font-weight:bold}\
+.summary td:first-child{font-weight:bold;width:40%}code{font-size:90%}";
+
+// Tri au clic sur l'en-tête (numérique si la cellule porte data-sort)
+const SCRIPT: &str = "document.querySelectorAll('table.sortable th').forEach(function(th,col){th.addEventListener('click',function(){\
+var table=th.closest('table'),body=table.tBodies[0],rows=Array.from(body.rows),asc=th.dataset.dir!=='asc';\
+table.querySelectorAll('th').forEach(function(h){delete h.dataset.dir});th.dataset.dir=asc?'asc':'desc';\
+function key(r){var c=r.cells[col];return c.dataset.sort!==undefined?parseFloat(c.dataset.sort):c.textContent.toLowerCase()}\
+rows.sort(function(a,b){var x=key(a),y=key(b);return (x<y?-1:x>y?1:0)*(asc?1:-1)});rows.forEach(function(r){body.appendChild(r)})})});";
+
+/// Rapport HTML autonome (aucune ressource externe) : statistiques, fichiers par dossier,
+/// groupes de doublons, fichiers sans date et plan de renommage (avant -> après).
+pub fn export_to_html(path: &str, data: &[FileAnalysis], title: &str) -> std::io::Result<()> {
+    fs::write(path, render_html_report(data, title))
+}
+
+pub fn render_html_report(data: &[FileAnalysis], title: &str) -> String {
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"fr\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n<p class=\"meta\">Généré le {date} par TimeShotRenamer</p>\n",
+        title = escape(title),
+        date = Local::now().format("%d/%m/%Y à %H:%M"),
+    );
+
+    let groups = find_duplicate_groups(data, &KeeperStrategy::Oldest);
+    let renames: Vec<&FileAnalysis> = data.iter().filter(|f| is_renamed(f)).collect();
+    let missing_dates: Vec<&FileAnalysis> = data.iter().filter(|f| f.best_date().0.is_none()).collect();
+    let total_size: u64 = data.iter().map(|f| f.file_size).sum();
+    let wasted: u64 = groups.iter().map(|g| g.wasted_bytes).sum();
+
+    // --- Résumé ---
+    html.push_str("<h2>Résumé</h2>\n<table class=\"summary\">\n");
+    let summary = [
+        ("Fichiers analysés", data.len().to_string()),
+        ("Taille totale", format_size(total_size)),
+        ("Avec date de prise de vue", data.iter().filter(|f| f.exif.date_taken.is_some()).count().to_string()),
+        ("Sans aucune date", missing_dates.len().to_string()),
+        ("Doublons exacts", format!("{} fichier(s) dans {} groupe(s), {} récupérables", groups.iter().map(|g| g.members.len()).sum::<usize>(), groups.len(), format_size(wasted))),
+        ("Doublons probables", data.iter().filter(|f| f.is_probable_duplicate).count().to_string()),
+        ("Renommages prévus", renames.len().to_string()),
+    ];
+    for (label, value) in summary {
+        let _ = writeln!(html, "<tr><td>{}</td><td>{}</td></tr>", label, escape(&value));
+    }
+    html.push_str("</table>\n");
+
+    // --- Plan de renommage ---
+    let _ = writeln!(html, "<h2>Plan de renommage ({})</h2>", renames.len());
+    if renames.is_empty() {
+        html.push_str("<p>Aucun renommage prévu.</p>\n");
+    } else {
+        html.push_str("<table class=\"sortable\">\n<thead><tr><th>Dossier</th><th>Nom actuel</th><th>Nouveau nom</th></tr></thead>\n<tbody>\n");
+        for f in &renames {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&parent_display(f)), escape(&f.original_name), escape(f.new_name.as_deref().unwrap_or(""))
+            );
+        }
+        html.push_str("</tbody>\n</table>\n");
+    }
+
+    // --- Doublons ---
+    let _ = writeln!(html, "<h2>Groupes de doublons ({})</h2>", groups.len());
+    if groups.is_empty() {
+        html.push_str("<p>Aucun doublon exact.</p>\n");
+    }
+    for group in &groups {
+        let _ = writeln!(
+            html,
+            "<h3>{} fichiers de {} — {} récupérables</h3>\n<ul>",
+            group.members.len(), format_size(group.file_size), format_size(group.wasted_bytes)
+        );
+        for &member in &group.members {
+            let path = escape(&data[member].full_original_path.to_string_lossy());
+            if member == group.keeper {
+                let _ = writeln!(html, "<li class=\"keeper\">{} (conservé)</li>", path);
+            } else {
+                let _ = writeln!(html, "<li>{}</li>", path);
+            }
+        }
+        let _ = writeln!(html, "</ul>\n<p class=\"meta\"><code>{}</code> : {}</p>", group.algorithm.name(), escape(&group.hash));
+    }
+
+    // --- Fichiers sans date ---
+    let _ = writeln!(html, "<h2>Fichiers sans date ({})</h2>", missing_dates.len());
+    if missing_dates.is_empty() {
+        html.push_str("<p>Tous les fichiers ont une date.</p>\n");
+    } else {
+        html.push_str("<ul>\n");
+        for f in &missing_dates {
+            let _ = writeln!(html, "<li>{}</li>", escape(&f.full_original_path.to_string_lossy()));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    // --- Fichiers par dossier ---
+    let mut by_folder: BTreeMap<String, Vec<&FileAnalysis>> = BTreeMap::new();
+    for f in data {
+        by_folder.entry(parent_display(f)).or_default().push(f);
+    }
+    let _ = writeln!(html, "<h2>Fichiers par dossier ({})</h2>", by_folder.len());
+    for (folder, files) in &by_folder {
+        let _ = writeln!(html, "<h3>{} ({} fichiers)</h3>", escape(folder), files.len());
+        html.push_str("<table class=\"sortable\">\n<thead><tr><th>Nom</th><th>Nom proposé</th><th>Date</th><th>Source de la date</th><th>Taille</th><th>Statut</th></tr></thead>\n<tbody>\n");
+        for f in files {
+            let (date, source) = f.best_date();
+            let (status, class) = if f.is_duplicate {
+                ("Doublon", "dup")
+            } else if f.is_probable_duplicate {
+                ("Doublon probable", "probable")
+            } else if f.hash_error.is_some() {
+                ("Erreur de lecture", "dup")
+            } else {
+                ("", "")
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td data-sort=\"{}\">{}</td><td class=\"{}\">{}</td></tr>",
+                escape(&f.original_name),
+                escape(f.new_name.as_deref().unwrap_or("")),
+                date.map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default(),
+                escape(source.label()),
+                f.file_size,
+                format_size(f.file_size),
+                class,
+                status,
+            );
+        }
+        html.push_str("</tbody>\n</table>\n");
+    }
+
+    let _ = write!(html, "<script>{}</script>\n</body>\n</html>\n", SCRIPT);
+    html
+}
+
+fn is_renamed(f: &FileAnalysis) -> bool {
+    f.new_name.as_deref().is_some_and(|name| !name.is_empty() && name != f.original_name)
+}
+
+fn parent_display(f: &FileAnalysis) -> String {
+    f.full_original_path.parent().unwrap_or(Path::new("")).to_string_lossy().into_owned()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::file_analysis;
+    use crate::types::DateSource;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn escapes_text_and_groups_files_by_folder() {
+        let date = NaiveDate::from_ymd_opt(2023, 5, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+        let mut dated = FileAnalysis { new_name: Some("2023 <b>&</b>.jpg".to_string()), ..file_analysis("/photos/a/<script>.jpg") };
+        dated.exif.date_taken = Some(date);
+        let duplicate = |path: &str| FileAnalysis { file_hash: Some("abc".to_string()), file_size: 10, is_duplicate: true, ..file_analysis(path) };
+        let data = [dated, duplicate("/photos/a/x.jpg"), duplicate("/photos/b/\"x\".jpg")];
+
+        let html = render_html_report(&data, "Rapport \"R&D\"");
+        assert!(html.contains("<title>Rapport &quot;R&amp;D&quot;</title>"));
+        assert!(html.contains("<td>&lt;script&gt;.jpg</td>"));
+        assert!(!html.contains("<script>.jpg"));
+        assert!(html.contains("2023 &lt;b&gt;&amp;&lt;/b&gt;.jpg"));
+        // Libellé lisible de l'origine de la date
+        assert!(html.contains(&format!("<td>{}</td>", DateSource::DateTimeOriginal.label())));
+
+        assert!(html.contains("<h2>Fichiers par dossier (2)</h2>"));
+        assert!(html.contains("<h3>/photos/a (2 fichiers)</h3>"));
+        assert!(html.contains("<h3>/photos/b (1 fichiers)</h3>"));
+        assert!(html.contains("<h2>Groupes de doublons (1)</h2>"));
+        assert!(html.contains("/photos/a/x.jpg") && html.contains("/photos/b/&quot;x&quot;.jpg"));
+        assert_eq!(html.matches("(conservé)").count(), 1);
+        assert!(html.contains("<h2>Fichiers sans date (2)</h2>"));
+    }
+}
@@ -1,2 +1,6 @@
 pub mod json;
-pub mod csv;
\ No newline at end of file
+pub mod csv;
+pub mod ndjson;
+pub mod html;
+pub mod checksums;
+pub mod session;
\ No newline at end of file
@@ -0,0 +1,39 @@
+// timeshot_core/src/export/ndjson.rs
+//
+// Export NDJSON : un objet JSON par fichier et par ligne, écrit au fil de l'analyse si besoin.
+
+use crate::types::{DateSource, FileAnalysis};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Ligne NDJSON : l'analyse complète, avec chemin complet et origine de la date.
+#[derive(Serialize)]
+struct NdjsonRecord<'a> {
+    full_path: &'a Path,
+    date_source: DateSource,
+    #[serde(flatten)]
+    analysis: &'a FileAnalysis,
+}
+
+/// Écrit un objet JSON par ligne (lisible ligne à ligne, sans charger tout le fichier).
+pub fn write_ndjson<W: Write>(writer: &mut W, data: &[FileAnalysis]) -> std::io::Result<()> {
+    for analysis in data {
+        write_ndjson_record(writer, analysis)?;
+    }
+    Ok(())
+}
+
+/// Écrit une seule ligne, pour produire la sortie au fil d'une analyse en flux.
+pub fn write_ndjson_record<W: Write>(writer: &mut W, analysis: &FileAnalysis) -> std::io::Result<()> {
+    let record = NdjsonRecord { full_path: &analysis.full_original_path, date_source: analysis.best_date().1, analysis };
+    serde_json::to_writer(&mut *writer, &record)?;
+    writer.write_all(b"\n")
+}
+
+pub fn export_to_ndjson(path: &str, data: &[FileAnalysis]) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_ndjson(&mut writer, data)?;
+    writer.flush()
+}
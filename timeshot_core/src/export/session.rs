@@ -0,0 +1,139 @@
+// timeshot_core/src/export/session.rs
+//
+// Session d'analyse sauvegardée (JSON versionné) : dossiers, options, analyses avec chemins
+// complets et modifications de l'utilisateur. Rechargeable pour appliquer un plan relu.
+
+use crate::types::{AnalysisOptions, FileAnalysis};
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Version du format ; une session plus récente que le programme est refusée.
+pub const SESSION_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub version: u32,
+    pub created: NaiveDateTime,
+    /// Dossiers analysés.
+    pub directories: Vec<PathBuf>,
+    pub options: AnalysisOptions,
+    pub files: Vec<SessionFile>,
+}
+
+/// Fichier d'une session : l'analyse et les choix de l'utilisateur.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionFile {
+    /// Chemin complet (absent de la sérialisation de `FileAnalysis`).
+    pub path: PathBuf,
+    #[serde(flatten)]
+    pub analysis: FileAnalysis,
+    #[serde(default)]
+    pub selected: bool,
+    /// Nom saisi par l'utilisateur, prioritaire sur le nom proposé.
+    #[serde(default)]
+    pub edited_name: Option<String>,
+}
+
+impl SessionFile {
+    pub fn new(analysis: FileAnalysis) -> Self {
+        SessionFile { path: analysis.full_original_path.clone(), analysis, selected: false, edited_name: None }
+    }
+
+    /// Nom à appliquer : nom modifié, sinon nom proposé.
+    pub fn target_name(&self) -> Option<&str> {
+        self.edited_name.as_deref().or(self.analysis.new_name.as_deref())
+    }
+}
+
+impl Session {
+    pub fn new(directories: Vec<PathBuf>, options: AnalysisOptions, files: Vec<SessionFile>) -> Self {
+        Session { version: SESSION_VERSION, created: Local::now().naive_local(), directories, options, files }
+    }
+
+    /// Session à partir de résultats d'analyse bruts (aucune sélection ni modification).
+    pub fn from_analyses(directories: Vec<PathBuf>, options: AnalysisOptions, analyses: &[FileAnalysis]) -> Self {
+        Session::new(directories, options, analyses.iter().cloned().map(SessionFile::new).collect())
+    }
+
+    /// Analyses des fichiers de la session (chemins complets inclus).
+    pub fn analyses(&self) -> Vec<FileAnalysis> {
+        self.files.iter().map(|f| f.analysis.clone()).collect()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| format!("Création de '{}' impossible: {}", path.display(), e))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, self).map_err(|e| format!("Sérialisation de la session impossible: {}", e))?;
+        writer.flush().map_err(|e| format!("Écriture de '{}' impossible: {}", path.display(), e))
+    }
+
+    pub fn load(path: &Path) -> Result<Session, String> {
+        let file = File::open(path).map_err(|e| format!("Ouverture de '{}' impossible: {}", path.display(), e))?;
+        let value: serde_json::Value = serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| format!("Session illisible ({}): {}", path.display(), e))?;
+        let version = value.get("version").and_then(|v| v.as_u64()).ok_or("Session sans numéro de version")?;
+        if version > SESSION_VERSION as u64 {
+            return Err(format!("Session en version {}, non prise en charge (version maximale : {})", version, SESSION_VERSION));
+        }
+        let mut session: Session = serde_json::from_value(value).map_err(|e| format!("Session invalide ({}): {}", path.display(), e))?;
+        for file in &mut session.files {
+            file.analysis.full_original_path = file.path.clone();
+        }
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{file_analysis, test_dir};
+    use crate::types::CacheMode;
+
+    #[test]
+    fn save_then_load_keeps_paths_options_and_user_choices() {
+        let dir = test_dir("session_round_trip");
+        let photos = dir.join("photos");
+        let options = AnalysisOptions {
+            recursive: true,
+            similarity_threshold: Some(7),
+            cache: CacheMode::Enabled,
+            name_template: Some("{date:%Y}_{stem}".to_string()),
+            ..Default::default()
+        };
+        let files = vec![
+            SessionFile { selected: true, edited_name: Some("vacances.jpg".to_string()), ..SessionFile::new(file_analysis(photos.join("a.jpg"))) },
+            SessionFile::new(FileAnalysis { new_name: Some("2023_b.jpg".to_string()), file_size: 42, ..file_analysis(photos.join("b.jpg")) }),
+        ];
+        let path = dir.join("session.json");
+        Session::new(vec![photos.clone()], options, files).save(&path).unwrap();
+
+        let session = Session::load(&path).unwrap();
+        assert_eq!(session.version, SESSION_VERSION);
+        assert_eq!(session.directories, vec![photos.clone()]);
+        assert!(session.options.recursive);
+        assert_eq!(session.options.similarity_threshold, Some(7));
+        assert_eq!(session.options.cache, CacheMode::Enabled);
+        assert_eq!(session.options.name_template.as_deref(), Some("{date:%Y}_{stem}"));
+        let paths: Vec<&Path> = session.files.iter().map(|f| f.path.as_path()).collect();
+        assert_eq!(paths, [photos.join("a.jpg"), photos.join("b.jpg")]);
+        // Le chemin complet, non sérialisé par `FileAnalysis`, est restauré
+        assert_eq!(session.analyses()[1].full_original_path, photos.join("b.jpg"));
+        assert_eq!(session.files[1].analysis.file_size, 42);
+        assert!(session.files[0].selected && !session.files[1].selected);
+        assert_eq!(session.files[0].target_name(), Some("vacances.jpg"));
+        assert_eq!(session.files[1].target_name(), Some("2023_b.jpg"));
+    }
+
+    #[test]
+    fn refuses_newer_session_versions() {
+        let dir = test_dir("session_version");
+        let path = dir.join("session.json");
+        let mut session = Session::new(Vec::new(), AnalysisOptions::default(), Vec::new());
+        session.version = SESSION_VERSION + 1;
+        session.save(&path).unwrap();
+        assert!(Session::load(&path).unwrap_err().contains("non prise en charge"));
+    }
+}
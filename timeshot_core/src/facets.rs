@@ -0,0 +1,187 @@
+// timeshot_core/src/facets.rs
+//
+// Filtres par facettes sur les résultats d'analyse : plage de dates et valeurs choisies
+// (appareil, objectif, type, présence d'une date EXIF, origine de la date, statut de doublon).
+
+use crate::types::FileAnalysis;
+use chrono::NaiveDate;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+/// Valeur affichée quand l'information manque (appareil ou objectif inconnu, fichier sans extension).
+pub const UNKNOWN_VALUE: &str = "(inconnu)";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Facet {
+    Camera,
+    Lens,
+    FileType,
+    HasExifDate,
+    DateSource,
+    DuplicateStatus,
+}
+
+impl Facet {
+    pub const ALL: [Facet; 6] = [Facet::Camera, Facet::Lens, Facet::FileType, Facet::HasExifDate, Facet::DateSource, Facet::DuplicateStatus];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Facet::Camera => "Appareil",
+            Facet::Lens => "Objectif",
+            Facet::FileType => "Type",
+            Facet::HasExifDate => "Date EXIF",
+            Facet::DateSource => "Origine de la date",
+            Facet::DuplicateStatus => "Doublons",
+        }
+    }
+
+    /// Valeur de la facette pour ce fichier.
+    pub fn value(self, analysis: &FileAnalysis) -> String {
+        let known = |value: &Option<String>| value.as_deref().map(str::trim).filter(|v| !v.is_empty()).unwrap_or(UNKNOWN_VALUE).to_string();
+        match self {
+            Facet::Camera => known(&analysis.exif.camera_model),
+            Facet::Lens => known(&analysis.exif.lens_model),
+            Facet::FileType => Path::new(&analysis.original_name)
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_else(|| UNKNOWN_VALUE.to_string()),
+            Facet::HasExifDate => {
+                let exif = &analysis.exif;
+                let has_date = exif.date_taken.or(exif.create_date).or(exif.modify_date).is_some();
+                if has_date { "Avec date EXIF" } else { "Sans date EXIF" }.to_string()
+            }
+            Facet::DateSource => analysis.best_date().1.label().to_string(),
+            Facet::DuplicateStatus => {
+                if analysis.hash_error.is_some() { "Erreur de lecture" }
+                else if analysis.is_duplicate { "Doublon" }
+                else if analysis.is_probable_duplicate { "Doublon probable" }
+                else { "Unique" }.to_string()
+            }
+        }
+    }
+}
+
+/// Nombre de fichiers par valeur de la facette, du plus fréquent au plus rare (puis par nom).
+pub fn facet_counts<'a>(analyses: impl IntoIterator<Item = &'a FileAnalysis>, facet: Facet) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for analysis in analyses {
+        *counts.entry(facet.value(analysis)).or_insert(0) += 1;
+    }
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// Critères actifs. Une facette sans valeur choisie ne filtre rien ; plusieurs valeurs d'une même
+/// facette se cumulent (ou), les facettes entre elles se combinent (et).
+#[derive(Debug, Clone, Default)]
+pub struct FacetFilter {
+    /// Bornes incluses, sur la meilleure date du fichier (`FileAnalysis::best_date`).
+    pub date_from: Option<NaiveDate>,
+    pub date_to: Option<NaiveDate>,
+    pub selected: HashMap<Facet, BTreeSet<String>>,
+}
+
+impl FacetFilter {
+    pub fn is_empty(&self) -> bool {
+        self.date_from.is_none() && self.date_to.is_none() && self.selected.values().all(BTreeSet::is_empty)
+    }
+
+    pub fn set_selected(&mut self, facet: Facet, value: &str, selected: bool) {
+        let values = self.selected.entry(facet).or_default();
+        if selected { values.insert(value.to_string()); } else { values.remove(value); }
+    }
+
+    pub fn is_selected(&self, facet: Facet, value: &str) -> bool {
+        self.selected.get(&facet).is_some_and(|values| values.contains(value))
+    }
+
+    pub fn selected_count(&self, facet: Facet) -> usize {
+        self.selected.get(&facet).map_or(0, BTreeSet::len)
+    }
+
+    pub fn matches(&self, analysis: &FileAnalysis) -> bool {
+        if self.date_from.is_some() || self.date_to.is_some() {
+            // Une plage de dates exclut les fichiers sans date
+            let Some(date) = analysis.best_date().0.map(|d| d.date()) else { return false };
+            if self.date_from.is_some_and(|from| date < from) || self.date_to.is_some_and(|to| date > to) { return false; }
+        }
+        self.selected.iter().all(|(facet, values)| values.is_empty() || values.contains(&facet.value(analysis)))
+    }
+}
+
+/// Date saisie par l'utilisateur (AAAA-MM-JJ) ; champ vide : pas de borne.
+pub fn parse_filter_date(text: &str) -> Result<Option<NaiveDate>, String> {
+    let text = text.trim();
+    if text.is_empty() { return Ok(None); }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").map(Some).map_err(|_| format!("Date invalide '{}' (format attendu : AAAA-MM-JJ)", text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::file_analysis;
+
+    fn photo(name: &str, camera: Option<&str>, date: Option<(i32, u32, u32)>) -> FileAnalysis {
+        let mut analysis = file_analysis(format!("/photos/{}", name));
+        analysis.exif.camera_model = camera.map(String::from);
+        analysis.exif.date_taken = date.and_then(|(y, m, d)| NaiveDate::from_ymd_opt(y, m, d)).and_then(|d| d.and_hms_opt(10, 0, 0));
+        analysis
+    }
+
+    fn matching(filter: &FacetFilter, files: &[FileAnalysis]) -> Vec<String> {
+        files.iter().filter(|f| filter.matches(f)).map(|f| f.original_name.clone()).collect()
+    }
+
+    #[test]
+    fn values_of_a_facet_are_or_facets_are_and() {
+        let files = [
+            photo("a.jpg", Some("X100"), Some((2023, 1, 1))),
+            photo("b.png", Some("X100"), Some((2023, 1, 2))),
+            photo("c.jpg", Some("EOS"), None),
+            photo("d.jpg", None, None),
+        ];
+        let mut filter = FacetFilter::default();
+        assert!(filter.is_empty());
+        assert_eq!(matching(&filter, &files).len(), 4);
+
+        filter.set_selected(Facet::Camera, "X100", true);
+        filter.set_selected(Facet::Camera, UNKNOWN_VALUE, true);
+        assert_eq!(matching(&filter, &files), ["a.jpg", "b.png", "d.jpg"]);
+        filter.set_selected(Facet::FileType, "jpg", true);
+        assert_eq!(matching(&filter, &files), ["a.jpg", "d.jpg"]);
+        assert_eq!(filter.selected_count(Facet::Camera), 2);
+
+        // Facette vidée : ne filtre plus
+        filter.set_selected(Facet::Camera, "X100", false);
+        filter.set_selected(Facet::Camera, UNKNOWN_VALUE, false);
+        assert!(!filter.is_selected(Facet::Camera, "X100"));
+        assert_eq!(matching(&filter, &files), ["a.jpg", "c.jpg", "d.jpg"]);
+    }
+
+    #[test]
+    fn date_bounds_are_inclusive_and_exclude_undated_files() {
+        let files = [
+            photo("a.jpg", None, Some((2023, 1, 1))),
+            photo("b.jpg", None, Some((2023, 6, 30))),
+            photo("c.jpg", None, Some((2024, 1, 1))),
+            photo("sans_date.jpg", None, None),
+        ];
+        let mut filter = FacetFilter { date_from: parse_filter_date("2023-06-30").unwrap(), ..Default::default() };
+        assert_eq!(matching(&filter, &files), ["b.jpg", "c.jpg"]);
+        filter.date_to = parse_filter_date(" 2023-06-30 ").unwrap();
+        assert_eq!(matching(&filter, &files), ["b.jpg"]);
+        filter.date_from = None;
+        assert_eq!(matching(&filter, &files), ["a.jpg", "b.jpg"]);
+    }
+
+    #[test]
+    fn parses_filter_dates() {
+        assert_eq!(parse_filter_date("").unwrap(), None);
+        assert_eq!(parse_filter_date("   ").unwrap(), None);
+        assert_eq!(parse_filter_date("2023-02-28").unwrap(), NaiveDate::from_ymd_opt(2023, 2, 28));
+        for text in ["2023-02-30", "28/02/2023", "2023-2", "demain"] {
+            assert!(parse_filter_date(text).unwrap_err().contains("AAAA-MM-JJ"), "{}", text);
+        }
+    }
+}
@@ -1,18 +1,165 @@
 
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
 use std::path::Path;
+use xxhash_rust::xxh3::Xxh3;
 
-pub fn compute_file_hash(path: &Path) -> Option<String> {
-    let file = File::open(path).ok()?;
+/// Algorithmes de hash disponibles (résultat en hexadécimal minuscule).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Blake3,
+    /// Compatible `sha256sum`.
+    Sha256,
+    /// XXH3 64 bits : très rapide, non cryptographique.
+    Xxh3,
+}
+
+impl HashAlgorithm {
+    pub const ALL: [HashAlgorithm; 3] = [HashAlgorithm::Blake3, HashAlgorithm::Sha256, HashAlgorithm::Xxh3];
+
+    /// Nom court ("blake3", "sha256", "xxh3").
+    pub fn name(self) -> &'static str {
+        match self {
+            HashAlgorithm::Blake3 => "blake3",
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Xxh3 => "xxh3",
+        }
+    }
+
+    /// Reconnaît le nom court, sans tenir compte de la casse ni des tirets ("SHA-256").
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalized = name.trim().to_lowercase().replace(['-', '_'], "");
+        HashAlgorithm::ALL.into_iter().find(|a| a.name() == normalized)
+    }
+
+    /// Longueur du hash en caractères hexadécimaux.
+    pub fn hex_len(self) -> usize {
+        match self {
+            HashAlgorithm::Blake3 | HashAlgorithm::Sha256 => 64,
+            HashAlgorithm::Xxh3 => 16,
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            HashAlgorithm::Blake3 => "BLAKE3",
+            HashAlgorithm::Sha256 => "SHA-256",
+            HashAlgorithm::Xxh3 => "xxh3",
+        })
+    }
+}
+
+enum Hasher {
+    Blake3(Box<blake3::Hasher>),
+    Sha256(Sha256),
+    Xxh3(Box<Xxh3>),
+}
+
+impl Hasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+            HashAlgorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            HashAlgorithm::Xxh3 => Hasher::Xxh3(Box::new(Xxh3::new())),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Blake3(h) => { h.update(data); }
+            Hasher::Sha256(h) => h.update(data),
+            Hasher::Xxh3(h) => h.update(data),
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Hasher::Blake3(h) => h.finalize().to_hex().to_string(),
+            Hasher::Sha256(h) => h.finalize().iter().map(|b| format!("{:02x}", b)).collect(),
+            Hasher::Xxh3(h) => format!("{:016x}", h.digest()),
+        }
+    }
+}
+
+/// Hash BLAKE3 du contenu complet (voir `compute_file_hash_with`).
+pub fn compute_file_hash(path: &Path) -> io::Result<String> {
+    compute_file_hash_with(path, HashAlgorithm::Blake3)
+}
+
+/// Hash du contenu complet avec l'algorithme choisi. Une erreur de lecture en cours de fichier
+/// est renvoyée (et non un hash du début du fichier).
+pub fn compute_file_hash_with(path: &Path, algorithm: HashAlgorithm) -> io::Result<String> {
+    let file = File::open(path)?;
     let mut reader = BufReader::new(file);
-    let mut hasher = blake3::Hasher::new();
+    let mut hasher = Hasher::new(algorithm);
 
     let mut buffer = [0u8; 8192];
-    while let Ok(n) = reader.read(&mut buffer) {
-        if n == 0 { break; }
+    loop {
+        let n = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
         hasher.update(&buffer[..n]);
     }
 
-    Some(hasher.finalize().to_hex().to_string())
+    Ok(hasher.finalize_hex())
+}
+
+/// Taille des échantillons lus en début et en fin de fichier pour le hash partiel.
+pub const PARTIAL_SAMPLE_SIZE: u64 = 64 * 1024;
+
+/// Hash BLAKE3 du premier et du dernier bloc de `PARTIAL_SAMPLE_SIZE` octets.
+/// Deux fichiers de même taille dont les hashes partiels diffèrent sont forcément différents.
+pub fn compute_partial_hash(path: &Path, file_size: u64) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buffer = vec![0u8; PARTIAL_SAMPLE_SIZE as usize];
+
+    let head_len = file_size.min(PARTIAL_SAMPLE_SIZE) as usize;
+    file.read_exact(&mut buffer[..head_len])?;
+    hasher.update(&buffer[..head_len]);
+
+    if file_size > PARTIAL_SAMPLE_SIZE {
+        let tail_len = (file_size - PARTIAL_SAMPLE_SIZE).min(PARTIAL_SAMPLE_SIZE) as usize;
+        file.seek(SeekFrom::Start(file_size - tail_len as u64))?;
+        file.read_exact(&mut buffer[..tail_len])?;
+        hasher.update(&buffer[..tail_len]);
+    }
+
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_dir;
+    use std::fs;
+
+    #[test]
+    fn digests_match_reference_values() {
+        let dir = test_dir("compute_known_answers");
+        let (empty, abc) = (dir.join("vide"), dir.join("abc"));
+        fs::write(&empty, "").unwrap();
+        fs::write(&abc, "abc").unwrap();
+        let cases = [
+            (HashAlgorithm::Blake3, "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262", "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"),
+            (HashAlgorithm::Sha256, "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+            (HashAlgorithm::Xxh3, "2d06800538d394c2", "78af5f94892f3950"),
+        ];
+        for (algorithm, empty_hash, abc_hash) in cases {
+            assert_eq!(compute_file_hash_with(&empty, algorithm).unwrap(), empty_hash, "{:?}", algorithm);
+            assert_eq!(compute_file_hash_with(&abc, algorithm).unwrap(), abc_hash, "{:?}", algorithm);
+            assert_eq!(abc_hash.len(), algorithm.hex_len());
+        }
+        assert_eq!(compute_file_hash(&abc).unwrap(), cases[0].2);
+    }
 }
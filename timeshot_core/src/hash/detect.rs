@@ -1,13 +1,68 @@
 
 use std::collections::HashMap;
 use crate::types::FileAnalysis;
+use super::compute::{HashAlgorithm, compute_file_hash_with, compute_partial_hash, PARTIAL_SAMPLE_SIZE};
+use super::perceptual::group_similar;
+
+/// Calcule `file_hash` uniquement pour les fichiers pouvant avoir un doublon :
+/// regroupement par taille, puis par hash partiel (début + fin), puis hash complet des candidats restants.
+/// Les fichiers de taille unique gardent `file_hash = None` (complété ensuite par `fill_full_hash`) ;
+/// le marquage des doublons est identique à celui obtenu en hachant tous les fichiers.
+pub fn hash_duplicate_candidates(files: &mut [FileAnalysis]) {
+    let mut by_size: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (i, file) in files.iter().enumerate() {
+        by_size.entry(file.file_size).or_default().push(i);
+    }
+
+    for (size, indices) in by_size {
+        if indices.len() < 2 { continue; }
+        // Petits fichiers : les échantillons couvrent déjà tout le contenu
+        if size <= 2 * PARTIAL_SAMPLE_SIZE {
+            for i in indices { fill_full_hash(&mut files[i]); }
+            continue;
+        }
+        let mut by_partial: HashMap<String, Vec<usize>> = HashMap::new();
+        // Hashes complets déjà connus (cache) : pas besoin d'échantillonner
+        let (known, unknown): (Vec<usize>, Vec<usize>) = indices.into_iter().partition(|&i| files[i].file_hash.is_some());
+        if !known.is_empty() {
+            for &i in &unknown { fill_full_hash(&mut files[i]); }
+            continue;
+        }
+        for i in unknown {
+            match compute_partial_hash(&files[i].full_original_path, size) {
+                Ok(partial) => by_partial.entry(partial).or_default().push(i),
+                Err(e) => record_hash_error(&mut files[i], e),
+            }
+        }
+        for candidates in by_partial.into_values().filter(|c| c.len() > 1) {
+            for i in candidates { fill_full_hash(&mut files[i]); }
+        }
+    }
+}
+
+/// Calcule le hash complet s'il manque (sans erreur de lecture déjà connue).
+pub fn fill_full_hash(file: &mut FileAnalysis) {
+    if file.file_hash.is_none() && file.hash_error.is_none() {
+        match compute_file_hash_with(&file.full_original_path, file.hash_algorithm) {
+            Ok(hash) => file.file_hash = Some(hash),
+            Err(e) => record_hash_error(file, e),
+        }
+    }
+}
+
+fn record_hash_error(file: &mut FileAnalysis, error: std::io::Error) {
+    eprintln!("Attention : hash impossible pour '{}': {}", file.full_original_path.display(), error);
+    file.file_hash = None;
+    file.hash_error = Some(error.to_string());
+}
 
 pub fn mark_duplicates(files: &mut [FileAnalysis]) {
-    let mut hash_map: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut hash_map: HashMap<(HashAlgorithm, String), Vec<usize>> = HashMap::new();
 
     for (i, file) in files.iter().enumerate() {
+        if file.hash_error.is_some() { continue; }
         if let Some(hash) = &file.file_hash {
-            hash_map.entry(hash.clone()).or_default().push(i);
+            hash_map.entry((file.hash_algorithm, hash.clone())).or_default().push(i);
         }
     }
 
@@ -19,3 +74,85 @@ pub fn mark_duplicates(files: &mut [FileAnalysis]) {
         }
     }
 }
+
+/// Marque comme doublons probables les images visuellement proches (hash perceptuel),
+/// lorsque leur groupe ne se réduit pas à des copies identiques octet par octet.
+pub fn mark_probable_duplicates(files: &mut [FileAnalysis], threshold: u32) {
+    for group in group_similar(files, threshold) {
+        let first_hash = &files[group[0]].file_hash;
+        let all_identical = first_hash.is_some() && group.iter().all(|&i| &files[i].file_hash == first_hash);
+        if all_identical { continue; }
+        for &i in &group {
+            files[i].is_probable_duplicate = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{file_analysis, test_dir};
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::path::Path;
+
+    fn analysis(path: &Path) -> FileAnalysis {
+        FileAnalysis { file_size: fs::metadata(path).unwrap().len(), ..file_analysis(path) }
+    }
+
+    /// Groupes de doublons exacts, par noms de fichiers.
+    fn duplicate_groups(files: &[FileAnalysis]) -> BTreeSet<BTreeSet<String>> {
+        let mut groups: HashMap<&str, BTreeSet<String>> = HashMap::new();
+        for file in files.iter().filter(|f| f.is_duplicate) {
+            groups.entry(file.file_hash.as_deref().unwrap()).or_default().insert(file.original_name.clone());
+        }
+        groups.into_values().collect()
+    }
+
+    #[test]
+    fn prefiltering_finds_the_same_groups_as_full_hashing() {
+        let dir = test_dir("detect_prefilter");
+        // Au-delà de deux échantillons : même début et même fin, milieu différent
+        let sample = PARTIAL_SAMPLE_SIZE as usize;
+        let large = |middle: u8| [vec![1u8; sample], vec![middle; 100], vec![2u8; sample]].concat();
+        let contents: [(&str, Vec<u8>); 9] = [
+            ("grand_a.bin", large(0)),
+            ("grand_a_copie.bin", large(0)),
+            ("grand_milieu.bin", large(9)),
+            ("unique.bin", vec![3u8; 10]),
+            ("vide_1.bin", Vec::new()),
+            ("vide_2.bin", Vec::new()),
+            ("petit_1.bin", b"abcd".to_vec()),
+            ("petit_2.bin", b"abce".to_vec()),
+            ("petit_copie.bin", b"abcd".to_vec()),
+        ];
+        for (name, content) in &contents { fs::write(dir.join(name), content).unwrap(); }
+        let analyses: Vec<FileAnalysis> = contents.iter().map(|(name, _)| analysis(&dir.join(name))).collect();
+
+        let mut full = analyses.clone();
+        full.iter_mut().for_each(fill_full_hash);
+        mark_duplicates(&mut full);
+
+        let mut prefiltered = analyses;
+        hash_duplicate_candidates(&mut prefiltered);
+        // Taille unique : pas de hash avant complétion
+        assert_eq!(prefiltered[3].file_hash, None);
+        mark_duplicates(&mut prefiltered);
+
+        let expected: BTreeSet<BTreeSet<String>> = [
+            vec!["grand_a.bin", "grand_a_copie.bin"],
+            vec!["vide_1.bin", "vide_2.bin"],
+            vec!["petit_1.bin", "petit_copie.bin"],
+        ]
+        .into_iter()
+        .map(|group| group.into_iter().map(String::from).collect())
+        .collect();
+        assert_eq!(duplicate_groups(&full), expected);
+        assert_eq!(duplicate_groups(&prefiltered), expected);
+
+        // Les fichiers restants complétés ensuite ont le même hash que par hachage complet
+        prefiltered.iter_mut().for_each(fill_full_hash);
+        let hashes = |files: &[FileAnalysis]| files.iter().map(|f| f.file_hash.clone()).collect::<Vec<_>>();
+        assert_eq!(hashes(&prefiltered), hashes(&full));
+    }
+}
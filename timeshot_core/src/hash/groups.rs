@@ -0,0 +1,208 @@
+// timeshot_core/src/hash/groups.rs
+//
+// Groupes de doublons exacts (même hash) et choix du fichier à conserver dans chaque groupe.
+
+use super::compute::HashAlgorithm;
+use crate::types::FileAnalysis;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Fichiers au contenu identique. Les membres sont des indices dans la liste analysée.
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateGroup {
+    pub hash: String,
+    pub algorithm: HashAlgorithm,
+    pub members: Vec<usize>,
+    /// Fichier conservé (l'un des `members`), selon la stratégie choisie.
+    pub keeper: usize,
+    /// Taille d'un exemplaire.
+    pub file_size: u64,
+    /// Espace récupérable en ne gardant que le `keeper`.
+    pub wasted_bytes: u64,
+}
+
+impl DuplicateGroup {
+    /// Membres à traiter (tous sauf le fichier conservé).
+    pub fn non_keepers(&self) -> impl Iterator<Item = usize> + '_ {
+        self.members.iter().copied().filter(move |&i| i != self.keeper)
+    }
+}
+
+/// Stratégies de choix du fichier à conserver.
+#[derive(Debug, Clone, Default)]
+pub enum KeeperStrategy {
+    /// Date de prise de vue la plus ancienne (à défaut, date de modification).
+    #[default]
+    Oldest,
+    /// Chemin complet le plus court.
+    ShortestPath,
+    /// Fichier avec le plus de métadonnées EXIF renseignées.
+    BestMetadata,
+    /// Premier fichier situé sous ce dossier ; sinon, le plus ancien.
+    PreferredFolder(PathBuf),
+}
+
+/// Regroupe les fichiers par hash (groupes d'au moins deux fichiers), triés par espace perdu décroissant.
+pub fn find_duplicate_groups(files: &[FileAnalysis], strategy: &KeeperStrategy) -> Vec<DuplicateGroup> {
+    let mut by_hash: HashMap<(HashAlgorithm, &str), Vec<usize>> = HashMap::new();
+    for (i, file) in files.iter().enumerate() {
+        // Fichiers dont la lecture a échoué : jamais regroupés
+        if file.hash_error.is_some() { continue; }
+        if let Some(hash) = &file.file_hash {
+            by_hash.entry((file.hash_algorithm, hash.as_str())).or_default().push(i);
+        }
+    }
+
+    let mut groups: Vec<DuplicateGroup> = by_hash
+        .into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|((algorithm, hash), members)| {
+            let file_size = files[members[0]].file_size;
+            let keeper = select_keeper(files, &members, strategy);
+            let wasted_bytes = file_size * (members.len() as u64 - 1);
+            DuplicateGroup { hash: hash.to_string(), algorithm, members, keeper, file_size, wasted_bytes }
+        })
+        .collect();
+    groups.sort_by(|a, b| b.wasted_bytes.cmp(&a.wasted_bytes).then_with(|| a.hash.cmp(&b.hash)));
+    groups
+}
+
+/// Choisit le fichier à conserver parmi `members` (non vide).
+/// En cas d'égalité, le chemin le plus court puis l'ordre alphabétique départagent.
+pub fn select_keeper(files: &[FileAnalysis], members: &[usize], strategy: &KeeperStrategy) -> usize {
+    let tie_break = |a: &usize, b: &usize| {
+        let (path_a, path_b) = (&files[*a].full_original_path, &files[*b].full_original_path);
+        path_a.as_os_str().len().cmp(&path_b.as_os_str().len()).then_with(|| path_a.cmp(path_b))
+    };
+    // Dates lues une fois avant les comparaisons (la date de modification demande un accès disque)
+    let ages: HashMap<usize, FileAge> = match strategy {
+        KeeperStrategy::Oldest | KeeperStrategy::PreferredFolder(_) => members.iter().map(|&i| (i, FileAge::of(&files[i]))).collect(),
+        _ => HashMap::new(),
+    };
+    let oldest = |a: &usize, b: &usize| ages[a].cmp(&ages[b]).then_with(|| tie_break(a, b));
+
+    let keeper = match strategy {
+        KeeperStrategy::Oldest => members.iter().min_by(|a, b| oldest(a, b)),
+        KeeperStrategy::ShortestPath => members.iter().min_by(|a, b| tie_break(a, b)),
+        KeeperStrategy::BestMetadata => members.iter().min_by(|a, b| {
+            metadata_score(&files[**b]).cmp(&metadata_score(&files[**a])).then_with(|| tie_break(a, b))
+        }),
+        KeeperStrategy::PreferredFolder(folder) => members
+            .iter()
+            .filter(|&&i| files[i].full_original_path.starts_with(folder))
+            .min_by(|a, b| tie_break(a, b))
+            .or_else(|| members.iter().min_by(|a, b| oldest(a, b))),
+    };
+    *keeper.expect("Un groupe de doublons contient au moins un fichier")
+}
+
+/// Âge d'un fichier : date de prise de vue, à défaut date de modification.
+#[derive(PartialEq, Eq)]
+struct FileAge {
+    date_taken: Option<NaiveDateTime>,
+    modified: Option<SystemTime>,
+}
+
+impl FileAge {
+    fn of(file: &FileAnalysis) -> Self {
+        let date_taken = file.exif.date_taken;
+        let modified = if date_taken.is_none() { file.full_original_path.metadata().and_then(|m| m.modified()).ok() } else { None };
+        FileAge { date_taken, modified }
+    }
+}
+
+impl Ord for FileAge {
+    /// Plus ancien d'abord ; les fichiers sans aucune date passent en dernier.
+    fn cmp(&self, other: &Self) -> Ordering {
+        fn known_first<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+            match (a, b) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        }
+        known_first(self.date_taken, other.date_taken).then_with(|| known_first(self.modified, other.modified))
+    }
+}
+
+impl PartialOrd for FileAge {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn metadata_score(file: &FileAnalysis) -> usize {
+    let exif = &file.exif;
+    let dates = [exif.date_taken, exif.create_date, exif.modify_date].iter().filter(|d| d.is_some()).count();
+    let texts = [&exif.artist, &exif.title, &exif.description, &exif.camera_model, &exif.lens_model]
+        .iter()
+        .filter(|t| t.is_some())
+        .count();
+    // La date de prise de vue compte double : c'est elle qui sert au renommage
+    dates + usize::from(exif.date_taken.is_some()) + texts + exif.keywords.len() + exif.other_fields.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{file_analysis, test_dir};
+    use chrono::NaiveDate;
+    use std::fs::{self, File};
+    use std::path::Path;
+    use std::time::Duration;
+
+    fn date(year: i32) -> Option<NaiveDateTime> {
+        NaiveDate::from_ymd_opt(year, 1, 1).unwrap().and_hms_opt(0, 0, 0)
+    }
+
+    fn duplicate(path: &Path) -> FileAnalysis {
+        FileAnalysis { file_hash: Some("abc".to_string()), file_size: 100, ..file_analysis(path) }
+    }
+
+    #[test]
+    fn keeper_follows_the_chosen_strategy() {
+        let dir = test_dir("groups_keeper");
+        fs::create_dir(dir.join("archives")).unwrap();
+        let mut files = vec![
+            duplicate(&dir.join("archives/copie_longue.jpg")),
+            duplicate(&dir.join("b.jpg")),
+            duplicate(&dir.join("recent.jpg")),
+            duplicate(&dir.join("sans_date.jpg")),
+        ];
+        files[1].exif.date_taken = date(2015);
+        files[2].exif.date_taken = date(2020);
+        files[2].exif.camera_model = Some("X100".to_string());
+        files[2].exif.artist = Some("Moi".to_string());
+        let members = [0, 1, 2, 3];
+
+        assert_eq!(select_keeper(&files, &members, &KeeperStrategy::Oldest), 1);
+        assert_eq!(select_keeper(&files, &members, &KeeperStrategy::ShortestPath), 1);
+        assert_eq!(select_keeper(&files, &members, &KeeperStrategy::BestMetadata), 2);
+        assert_eq!(select_keeper(&files, &members, &KeeperStrategy::PreferredFolder(dir.join("archives"))), 0);
+        // Aucun membre dans le dossier préféré : le plus ancien
+        assert_eq!(select_keeper(&files, &members, &KeeperStrategy::PreferredFolder(dir.join("ailleurs"))), 1);
+
+        let groups = find_duplicate_groups(&files, &KeeperStrategy::Oldest);
+        assert_eq!(groups.len(), 1);
+        assert_eq!((groups[0].keeper, groups[0].wasted_bytes), (1, 300));
+        assert_eq!(groups[0].non_keepers().collect::<Vec<_>>(), [0, 2, 3]);
+    }
+
+    #[test]
+    fn oldest_falls_back_to_modification_time_without_exif_date() {
+        let dir = test_dir("groups_mtime");
+        let (old, new) = (dir.join("zz_ancien.jpg"), dir.join("a_recent.jpg"));
+        for (path, age) in [(&old, 3600), (&new, 60)] {
+            File::create(path).unwrap().set_modified(SystemTime::now() - Duration::from_secs(age)).unwrap();
+        }
+        // Fichier disparu (sans date) : en dernier
+        let files = [duplicate(&dir.join("absent.jpg")), duplicate(&new), duplicate(&old)];
+        assert_eq!(select_keeper(&files, &[0, 1, 2], &KeeperStrategy::Oldest), 2);
+        assert_eq!(select_keeper(&files, &[0, 1], &KeeperStrategy::Oldest), 1);
+    }
+}
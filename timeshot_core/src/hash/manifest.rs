@@ -0,0 +1,149 @@
+// timeshot_core/src/hash/manifest.rs
+//
+// Manifeste de sommes de contrôle (chemin relatif + hash) et vérification d'un dossier
+// par rapport à un manifeste exporté précédemment.
+
+use super::compute::{compute_file_hash_with, HashAlgorithm};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Chemin relatif à la racine du manifeste.
+    pub path: PathBuf,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    pub algorithm: HashAlgorithm,
+    pub entries: Vec<ManifestEntry>,
+}
+
+/// Résultat de `verify_manifest` (chemins relatifs à la racine vérifiée).
+#[derive(Debug, Default)]
+pub struct VerificationReport {
+    /// Fichiers présents avec le hash attendu.
+    pub verified: usize,
+    pub missing: Vec<PathBuf>,
+    /// Fichiers dont le contenu ne correspond plus au manifeste.
+    pub changed: Vec<PathBuf>,
+    /// Fichiers présents sur le disque mais absents du manifeste.
+    pub new_files: Vec<PathBuf>,
+    pub errors: Vec<String>,
+}
+
+impl VerificationReport {
+    /// Vrai si le dossier correspond exactement au manifeste.
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.changed.is_empty() && self.new_files.is_empty() && self.errors.is_empty()
+    }
+}
+
+impl Manifest {
+    /// Manifeste JSON (`{"algorithm": "blake3", "entries": [...]}`).
+    pub fn load(path: &Path) -> io::Result<Manifest> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+}
+
+/// Hache tous les fichiers de `root` (triés par chemin). Les fichiers illisibles sont
+/// absents du manifeste et signalés dans la liste d'erreurs renvoyée.
+pub fn build_manifest(root: &Path, algorithm: HashAlgorithm, recursive: bool) -> (Manifest, Vec<String>) {
+    let mut manifest = Manifest { algorithm, entries: Vec::new() };
+    let mut errors = Vec::new();
+    for relative in list_files(root, recursive, &mut errors) {
+        match compute_file_hash_with(&root.join(&relative), algorithm) {
+            Ok(hash) => manifest.entries.push(ManifestEntry { path: relative, hash }),
+            Err(e) => errors.push(format!("Hash impossible pour '{}': {}", relative.display(), e)),
+        }
+    }
+    (manifest, errors)
+}
+
+/// Re-hache les fichiers du manifeste sous `root` et signale les fichiers manquants, modifiés et nouveaux.
+/// `ignored` : fichiers à ne pas signaler comme nouveaux (ex: le fichier du manifeste lui-même).
+pub fn verify_manifest(manifest: &Manifest, root: &Path, recursive: bool, ignored: &[PathBuf]) -> VerificationReport {
+    let mut report = VerificationReport::default();
+    let mut known: HashSet<&Path> = HashSet::new();
+
+    for entry in &manifest.entries {
+        known.insert(entry.path.as_path());
+        let full_path = root.join(&entry.path);
+        if !full_path.is_file() {
+            report.missing.push(entry.path.clone());
+            continue;
+        }
+        match compute_file_hash_with(&full_path, manifest.algorithm) {
+            Ok(hash) if hash.eq_ignore_ascii_case(&entry.hash) => report.verified += 1,
+            Ok(_) => report.changed.push(entry.path.clone()),
+            Err(e) => report.errors.push(format!("Hash impossible pour '{}': {}", entry.path.display(), e)),
+        }
+    }
+
+    for relative in list_files(root, recursive, &mut report.errors) {
+        if known.contains(relative.as_path()) { continue; }
+        if ignored.iter().any(|p| p == &root.join(&relative) || p == &relative) { continue; }
+        report.new_files.push(relative);
+    }
+    report
+}
+
+/// Fichiers sous `root`, en chemins relatifs triés.
+fn list_files(root: &Path, recursive: bool, errors: &mut Vec<String>) -> Vec<PathBuf> {
+    let mut walker = WalkDir::new(root).min_depth(1).sort_by_file_name();
+    if !recursive { walker = walker.max_depth(1); }
+    let mut files = Vec::new();
+    for entry in walker {
+        match entry {
+            Ok(entry) if entry.file_type().is_file() => {
+                if let Ok(relative) = entry.path().strip_prefix(root) { files.push(relative.to_path_buf()); }
+            }
+            Ok(_) => {}
+            Err(e) => errors.push(format!("Erreur lecture entrée dans {}: {}", root.display(), e)),
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_dir;
+    use std::fs;
+
+    #[test]
+    fn verification_reports_changed_missing_and_new_files() {
+        let dir = test_dir("manifest_verify");
+        fs::create_dir(dir.join("sous")).unwrap();
+        for (path, content) in [("a.jpg", "a"), ("b.jpg", "b"), ("sous/c.jpg", "c")] { fs::write(dir.join(path), content).unwrap(); }
+        let (manifest, errors) = build_manifest(&dir, HashAlgorithm::Sha256, true);
+        assert!(errors.is_empty());
+        assert_eq!(manifest.entries.len(), 3);
+        let manifest_path = dir.join("manifeste.json");
+        manifest.save(&manifest_path).unwrap();
+        let manifest = Manifest::load(&manifest_path).unwrap();
+        assert!(verify_manifest(&manifest, &dir, true, std::slice::from_ref(&manifest_path)).is_clean());
+
+        fs::write(dir.join("a.jpg"), "modifié").unwrap();
+        fs::remove_file(dir.join("sous/c.jpg")).unwrap();
+        fs::write(dir.join("nouveau.jpg"), "n").unwrap();
+        let report = verify_manifest(&manifest, &dir, true, &[manifest_path]);
+        assert_eq!(report.verified, 1);
+        assert_eq!(report.changed, [PathBuf::from("a.jpg")]);
+        assert_eq!(report.missing, [PathBuf::from("sous/c.jpg")]);
+        assert_eq!(report.new_files, [PathBuf::from("nouveau.jpg")]);
+        assert!(report.errors.is_empty() && !report.is_clean());
+    }
+}
@@ -1,2 +1,6 @@
 pub mod compute;
-pub mod detect;
\ No newline at end of file
+pub mod detect;
+pub mod groups;
+pub mod manifest;
+pub mod perceptual;
+pub mod resolve;
\ No newline at end of file
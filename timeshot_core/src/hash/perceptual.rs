@@ -0,0 +1,105 @@
+// timeshot_core/src/hash/perceptual.rs
+//
+// Hash perceptuel (dHash) : deux images visuellement proches (redimensionnées, ré-encodées)
+// ont des hashes à faible distance de Hamming, contrairement au BLAKE3.
+
+use crate::exif::thumbnail::extract_embedded_thumbnail;
+use crate::types::FileAnalysis;
+use image::imageops::FilterType;
+use image::DynamicImage;
+use std::path::Path;
+
+/// Distance de Hamming par défaut en dessous de laquelle deux images sont des doublons probables.
+pub const DEFAULT_SIMILARITY_THRESHOLD: u32 = 10;
+
+const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "bmp", "tif", "tiff", "webp"];
+
+/// Calcule le dHash 64 bits d'une image (voir `dhash`). La miniature EXIF suffit pour une vignette
+/// 9x8 et évite de décoder l'image entière ; sans miniature, l'image est décodée.
+/// Renvoie `None` pour les fichiers qui ne sont pas des images décodables.
+pub fn compute_perceptual_hash(path: &Path) -> Option<u64> {
+    let extension = path.extension()?.to_string_lossy().to_lowercase();
+    if !IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        return None;
+    }
+    let embedded = extract_embedded_thumbnail(path).and_then(|bytes| image::load_from_memory(&bytes).ok());
+    let image = match embedded.map_or_else(|| image::open(path), Ok) {
+        Ok(image) => image,
+        Err(e) => {
+            log::debug!("Décodage impossible pour le hash perceptuel de {:?}: {}", path, e);
+            return None;
+        }
+    };
+    Some(dhash(&image))
+}
+
+/// dHash 64 bits : vignette 9x8 en niveaux de gris, un bit par comparaison de pixels voisins sur chaque ligne.
+pub fn dhash(image: &DynamicImage) -> u64 {
+    let thumbnail = image.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            let left = thumbnail.get_pixel(x, y)[0];
+            let right = thumbnail.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | u64::from(left > right);
+        }
+    }
+    hash
+}
+
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Regroupe les fichiers dont les hashes perceptuels sont à une distance <= `threshold` de la première
+/// image du groupe (ordre de `files`). Pas de regroupement de proche en proche : une suite d'images
+/// légèrement différentes (rafale, dégradé) ne forme pas un seul groupe, et deux images d'un même groupe
+/// sont au plus à `2 * threshold`. Seuls les groupes d'au moins deux fichiers sont renvoyés,
+/// sous forme d'indices dans `files`.
+pub fn group_similar(files: &[FileAnalysis], threshold: u32) -> Vec<Vec<usize>> {
+    // (hash de référence, indices) : comparaison à chaque référence, O(fichiers x groupes)
+    let mut groups: Vec<(u64, Vec<usize>)> = Vec::new();
+    for (i, hash) in files.iter().enumerate().filter_map(|(i, f)| f.perceptual_hash.map(|h| (i, h))) {
+        match groups.iter_mut().find(|(reference, _)| hamming_distance(*reference, hash) <= threshold) {
+            Some((_, members)) => members.push(i),
+            None => groups.push((hash, vec![i])),
+        }
+    }
+    groups.into_iter().map(|(_, members)| members).filter(|g| g.len() > 1).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::file_analysis;
+    use image::{GrayImage, Luma};
+
+    /// Image lisse (dégradés croisés), stable au redimensionnement.
+    fn pattern(width: u32, height: u32, frequency: f32) -> DynamicImage {
+        DynamicImage::ImageLuma8(GrayImage::from_fn(width, height, |x, y| {
+            let (fx, fy) = (x as f32 / width as f32, y as f32 / height as f32);
+            Luma([(128.0 + 100.0 * (fx * frequency).sin() * (fy * 5.0).cos()) as u8])
+        }))
+    }
+
+    #[test]
+    fn dhash_is_stable_for_resized_images_and_differs_otherwise() {
+        let original = pattern(320, 240, 9.0);
+        assert_eq!(dhash(&original), dhash(&pattern(320, 240, 9.0)));
+        let resized = original.resize_exact(96, 72, FilterType::Lanczos3);
+        assert!(hamming_distance(dhash(&original), dhash(&resized)) <= 2);
+        let different = pattern(320, 240, 20.0);
+        assert!(hamming_distance(dhash(&original), dhash(&different)) > DEFAULT_SIMILARITY_THRESHOLD);
+    }
+
+    #[test]
+    fn groups_do_not_chain_beyond_the_threshold() {
+        let with_hash = |hash: u64| FileAnalysis { perceptual_hash: Some(hash), ..file_analysis("img.jpg") };
+        // Chaque image est à 4 de la précédente, mais la troisième est à 8 de la première
+        let files = [with_hash(0), with_hash(0b1111), with_hash(0b1111_1111), with_hash(0b1_1111_1111), file_analysis("sans_hash.jpg")];
+        assert_eq!(group_similar(&files, 4), vec![vec![0, 1], vec![2, 3]]);
+        assert_eq!(group_similar(&files, 8), vec![vec![0, 1, 2]]);
+        assert_eq!(group_similar(&files, 2), vec![vec![2, 3]]);
+    }
+}
@@ -0,0 +1,259 @@
+// timeshot_core/src/hash/resolve.rs
+//
+// Actions sur les doublons exacts : quarantaine, remplacement par lien physique, corbeille.
+// Rien n'est supprimé définitivement : chaque fichier retiré est déplacé et l'opération
+// est enregistrée dans le journal pour pouvoir être annulée. L'espace n'est libéré qu'en
+// vidant la corbeille de l'application (`journal::trash::empty_trash`).
+
+use super::compute::{compute_file_hash_with, HashAlgorithm};
+use super::groups::DuplicateGroup;
+use crate::journal::entry::Operation;
+use crate::journal::store::{new_batch_id, Journal};
+use crate::types::FileAnalysis;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub enum ResolutionAction {
+    /// Déplace les doublons dans ce dossier en conservant leur arborescence.
+    Quarantine(PathBuf),
+    /// Remplace chaque doublon par un lien physique vers le fichier conservé.
+    Hardlink,
+    /// Déplace les doublons dans la corbeille de l'application (à côté du journal).
+    Trash,
+}
+
+#[derive(Debug, Default)]
+pub struct ResolutionReport {
+    /// Lot du journal (absent si rien n'a été fait).
+    pub batch_id: Option<String>,
+    pub resolved: usize,
+    /// Taille des doublons déplacés (quarantaine, corbeille, sauvegardes des liens physiques) :
+    /// toujours sur le disque tant que la quarantaine ou la corbeille n'est pas vidée.
+    pub moved_bytes: u64,
+    /// Chemins retirés de leur emplacement d'origine (quarantaine, corbeille).
+    pub removed_paths: Vec<PathBuf>,
+    pub errors: Vec<String>,
+}
+
+/// Applique l'action à tous les fichiers non conservés des groupes donnés.
+/// `base_dirs` (dossiers analysés) sert à calculer les chemins relatifs en quarantaine.
+pub fn resolve_duplicates(
+    files: &[FileAnalysis],
+    groups: &[DuplicateGroup],
+    action: &ResolutionAction,
+    base_dirs: &[PathBuf],
+    journal: &Journal,
+) -> ResolutionReport {
+    let batch_id = new_batch_id();
+    let mut report = ResolutionReport::default();
+
+    for group in groups {
+        let keeper_path = &files[group.keeper].full_original_path;
+        if compute_file_hash_with(keeper_path, group.algorithm).ok().as_deref() != Some(group.hash.as_str()) {
+            report.errors.push(format!("Fichier conservé absent ou modifié, groupe ignoré : {}", keeper_path.display()));
+            continue;
+        }
+        for index in group.non_keepers() {
+            let path = &files[index].full_original_path;
+            let result = verify_duplicate(path, &group.hash, group.algorithm)
+                .and_then(|_| resolve_one(path, keeper_path, action, base_dirs, journal, &batch_id));
+            match result {
+                Ok(operation) => {
+                    report.resolved += 1;
+                    report.moved_bytes += group.file_size;
+                    if !matches!(operation, Operation::HardlinkReplace { .. }) {
+                        report.removed_paths.push(path.clone());
+                    }
+                }
+                Err(e) => { eprintln!("Attention : {}", e); report.errors.push(e); }
+            }
+        }
+    }
+
+    if report.resolved > 0 { report.batch_id = Some(batch_id); }
+    report
+}
+
+/// Déplace un fichier, avec repli copie + suppression entre deux systèmes de fichiers.
+pub fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if to.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("'{}' existe déjà", to.display())));
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)?;
+    fs::remove_file(from)
+}
+
+fn verify_duplicate(path: &Path, expected_hash: &str, algorithm: HashAlgorithm) -> Result<(), String> {
+    match compute_file_hash_with(path, algorithm) {
+        Ok(hash) if hash == expected_hash => Ok(()),
+        Ok(_) => Err(format!("Fichier modifié depuis l'analyse, ignoré : {}", path.display())),
+        Err(e) => Err(format!("Fichier illisible, ignoré : {} ({})", path.display(), e)),
+    }
+}
+
+fn resolve_one(
+    path: &Path,
+    keeper_path: &Path,
+    action: &ResolutionAction,
+    base_dirs: &[PathBuf],
+    journal: &Journal,
+    batch_id: &str,
+) -> Result<Operation, String> {
+    let trash_target = || journal.trash_dir().join(batch_id).join(relative_location(path, base_dirs));
+    let operation = match action {
+        ResolutionAction::Quarantine(dir) => Operation::Quarantine {
+            from: path.to_path_buf(),
+            to: dir.join(relative_location(path, base_dirs)),
+        },
+        ResolutionAction::Trash => Operation::Trash { from: path.to_path_buf(), to: trash_target() },
+        ResolutionAction::Hardlink => Operation::HardlinkReplace {
+            path: path.to_path_buf(),
+            keeper: keeper_path.to_path_buf(),
+            backup: trash_target(),
+        },
+    };
+
+    journal
+        .append(batch_id, vec![operation.clone()])
+        .map_err(|e| format!("Écriture du journal impossible: {}", e))?;
+    let result = match &operation {
+        Operation::Quarantine { from, to } | Operation::Trash { from, to } => move_file(from, to),
+        Operation::HardlinkReplace { path, keeper, backup } => {
+            move_file(path, backup).and_then(|_| fs::hard_link(keeper, path).or_else(|e| {
+                // Lien impossible (autre volume...) : on remet le fichier d'origine
+                move_file(backup, path)?;
+                Err(e)
+            }))
+        }
+        _ => unreachable!(),
+    };
+    if let Err(e) = result {
+        let _ = journal.remove_entries(|entry| entry.batch_id == batch_id && entry.operation.path() == path);
+        return Err(format!("Traitement du doublon '{}' impossible: {}", path.display(), e));
+    }
+    Ok(operation)
+}
+
+/// Chemin relatif à conserver : à partir du dossier analysé qui contient le fichier (nom du dossier
+/// inclus, ou son chemin complet sans racine si un autre dossier analysé porte le même nom, ex:
+/// `/a/Photos` et `/b/Photos`), sinon le chemin complet sans sa racine.
+fn relative_location(path: &Path, base_dirs: &[PathBuf]) -> PathBuf {
+    for base in base_dirs {
+        if let Ok(relative) = path.strip_prefix(base) {
+            let same_name = base_dirs.iter().filter(|other| other.file_name() == base.file_name()).count() > 1;
+            let base_location = if same_name { without_root(base) } else { base.file_name().map(PathBuf::from).unwrap_or_default() };
+            return base_location.join(relative);
+        }
+    }
+    without_root(path)
+}
+
+fn without_root(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::groups::{find_duplicate_groups, KeeperStrategy};
+    use crate::journal::undo::undo_batch;
+    use crate::test_support::{file_analysis, test_dir};
+
+    /// Écrit `content` dans chaque fichier et renvoie les analyses (hash complet renseigné).
+    fn analysed_files(paths: &[PathBuf], content: &str) -> Vec<FileAnalysis> {
+        paths
+            .iter()
+            .map(|path| {
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, content).unwrap();
+                let file_hash = Some(compute_file_hash_with(path, HashAlgorithm::Blake3).unwrap());
+                FileAnalysis { file_size: content.len() as u64, file_hash, hash_algorithm: HashAlgorithm::Blake3, ..file_analysis(path) }
+            })
+            .collect()
+    }
+
+    /// Applique l'action (fichier conservé : chemin le plus court), vérifie le résultat, puis annule le lot.
+    fn resolve_and_undo(name: &str, action: impl Fn(&Path) -> ResolutionAction, check: impl Fn(&Path, &Path, &Journal)) {
+        let dir = test_dir(name);
+        let base = dir.join("photos");
+        let (keeper, duplicate) = (base.join("a.jpg"), base.join("sous/copie.jpg"));
+        let files = analysed_files(&[keeper.clone(), duplicate.clone()], "contenu");
+        let groups = find_duplicate_groups(&files, &KeeperStrategy::ShortestPath);
+        let journal = Journal::new(dir.join("journal.jsonl"));
+
+        let report = resolve_duplicates(&files, &groups, &action(&dir), std::slice::from_ref(&base), &journal);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!((report.resolved, report.moved_bytes), (1, 7));
+        check(&dir, &duplicate, &journal);
+
+        let undo = undo_batch(&journal, report.batch_id.as_deref().unwrap()).unwrap();
+        assert!(undo.errors.is_empty(), "{:?}", undo.errors);
+        assert_eq!(fs::read_to_string(&duplicate).unwrap(), "contenu");
+        assert_eq!(fs::read_to_string(&keeper).unwrap(), "contenu");
+        assert!(journal.read_all().unwrap().is_empty());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            assert_ne!(fs::metadata(&duplicate).unwrap().ino(), fs::metadata(&keeper).unwrap().ino());
+        }
+    }
+
+    #[test]
+    fn quarantine_moves_duplicate_and_undo_restores_it() {
+        resolve_and_undo("resolve_quarantine", |dir| ResolutionAction::Quarantine(dir.join("quarantaine")), |dir, duplicate, _| {
+            assert!(!duplicate.exists());
+            assert_eq!(fs::read_to_string(dir.join("quarantaine/photos/sous/copie.jpg")).unwrap(), "contenu");
+        });
+    }
+
+    #[test]
+    fn trash_moves_duplicate_and_undo_restores_it() {
+        resolve_and_undo("resolve_trash", |_| ResolutionAction::Trash, |_, duplicate, journal| {
+            assert!(!duplicate.exists());
+            assert_eq!(crate::journal::trash::trash_usage(journal).unwrap(), (1, 7));
+        });
+    }
+
+    #[test]
+    fn hardlink_replaces_duplicate_and_undo_restores_it() {
+        resolve_and_undo("resolve_hardlink", |_| ResolutionAction::Hardlink, |dir, duplicate, journal| {
+            assert_eq!(fs::read_to_string(duplicate).unwrap(), "contenu");
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::MetadataExt;
+                assert_eq!(fs::metadata(duplicate).unwrap().ino(), fs::metadata(dir.join("photos/a.jpg")).unwrap().ino());
+            }
+            // Copie de sauvegarde dans la corbeille jusqu'à l'annulation
+            assert_eq!(crate::journal::trash::trash_usage(journal).unwrap().0, 1);
+        });
+    }
+
+    #[test]
+    fn roots_with_the_same_name_get_distinct_quarantine_paths() {
+        let dir = test_dir("resolve_same_name_roots");
+        let (root_a, root_b) = (dir.join("a/Photos"), dir.join("b/Photos"));
+        let paths = [dir.join("garde.jpg"), root_a.join("x.jpg"), root_b.join("x.jpg")];
+        let files = analysed_files(&paths, "identique");
+        let groups = find_duplicate_groups(&files, &KeeperStrategy::ShortestPath);
+        let journal = Journal::new(dir.join("journal.jsonl"));
+        let quarantine = dir.join("quarantaine");
+
+        let report = resolve_duplicates(&files, &groups, &ResolutionAction::Quarantine(quarantine.clone()), &[root_a.clone(), root_b.clone()], &journal);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.resolved, 2);
+        assert!(quarantine.join(without_root(&root_a)).join("x.jpg").is_file());
+        assert!(quarantine.join(without_root(&root_b)).join("x.jpg").is_file());
+        // Noms de dossiers distincts : seul le nom du dossier analysé est gardé
+        assert_eq!(relative_location(&root_a.join("x.jpg"), &[root_a.clone(), dir.join("Autres")]), PathBuf::from("Photos/x.jpg"));
+    }
+}
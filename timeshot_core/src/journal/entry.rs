@@ -0,0 +1,77 @@
+// timeshot_core/src/journal/entry.rs
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Une ligne du journal : une opération appliquée sur le disque, rattachée à un lot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub batch_id: String,
+    pub timestamp: NaiveDateTime,
+    pub operation: Operation,
+}
+
+/// Opérations réversibles enregistrées dans le journal.
+/// Chaque variante contient ce qu'il faut pour revenir à l'état précédent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Operation {
+    /// Dates EXIF réécrites directement dans le fichier (JPEG/TIFF).
+    ExifDateWrite {
+        path: PathBuf,
+        previous_date: Option<String>,
+        previous_offset: Option<String>,
+        new_date: String,
+        new_offset: Option<String>,
+    },
+    /// Sidecar XMP créé ou remplacé (contenu précédent conservé s'il existait).
+    XmpSidecarWrite {
+        path: PathBuf,
+        sidecar_path: PathBuf,
+        previous_content: Option<String>,
+    },
+    /// Dates de modification / d'accès du système de fichiers remplacées.
+    SetFileTimes {
+        path: PathBuf,
+        previous_modified: SystemTime,
+        previous_accessed: SystemTime,
+        new_time: SystemTime,
+    },
+    /// Fichier renommé (ou déplacé) par l'application.
+    Rename { from: PathBuf, to: PathBuf },
+    /// Doublon déplacé dans un dossier de quarantaine.
+    Quarantine { from: PathBuf, to: PathBuf },
+    /// Doublon déplacé dans la corbeille de l'application.
+    Trash { from: PathBuf, to: PathBuf },
+    /// Doublon remplacé par un lien physique vers `keeper`, l'original étant mis de côté dans `backup`.
+    HardlinkReplace { path: PathBuf, keeper: PathBuf, backup: PathBuf },
+}
+
+impl Operation {
+    /// Fichier principal concerné par l'opération.
+    pub fn path(&self) -> &PathBuf {
+        match self {
+            Operation::ExifDateWrite { path, .. } => path,
+            Operation::XmpSidecarWrite { path, .. } => path,
+            Operation::SetFileTimes { path, .. } => path,
+            Operation::Rename { from, .. } | Operation::Quarantine { from, .. } | Operation::Trash { from, .. } => from,
+            Operation::HardlinkReplace { path, .. } => path,
+        }
+    }
+
+    /// Description courte pour l'historique (ex: "Renommage : a.jpg → b.jpg").
+    pub fn describe(&self) -> String {
+        let name = |path: &PathBuf| path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_else(|| path.display().to_string());
+        match self {
+            Operation::ExifDateWrite { path, new_date, .. } => format!("Date EXIF : {} ({})", name(path), new_date),
+            Operation::XmpSidecarWrite { sidecar_path, .. } => format!("Sidecar XMP : {}", name(sidecar_path)),
+            Operation::SetFileTimes { path, .. } => format!("Dates du fichier : {}", name(path)),
+            Operation::Rename { from, to } => format!("Renommage : {} → {}", name(from), name(to)),
+            Operation::Quarantine { from, to } => format!("Quarantaine : {} → {}", from.display(), to.display()),
+            Operation::Trash { from, .. } => format!("Corbeille : {}", from.display()),
+            Operation::HardlinkReplace { path, keeper, .. } => format!("Lien physique : {} → {}", path.display(), keeper.display()),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+// timeshot_core/src/journal/mod.rs
+
+pub mod entry;
+pub mod store;
+pub mod trash;
+pub mod undo;
@@ -0,0 +1,135 @@
+// timeshot_core/src/journal/store.rs
+
+use super::entry::{JournalEntry, Operation};
+use chrono::{Local, NaiveDateTime};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+const JOURNAL_FILE_NAME: &str = "journal.jsonl";
+
+/// Journal d'annulation : un fichier JSON Lines, une entrée par ligne, en ajout seul.
+#[derive(Debug, Clone)]
+pub struct Journal {
+    path: PathBuf,
+}
+
+/// Opérations d'un même lot (une action de l'utilisateur).
+#[derive(Debug, Clone)]
+pub struct JournalBatch {
+    pub batch_id: String,
+    /// Horodatage de la première opération du lot.
+    pub timestamp: NaiveDateTime,
+    pub entries: Vec<JournalEntry>,
+}
+
+impl JournalBatch {
+    /// Le lot contient au moins un renommage.
+    pub fn has_renames(&self) -> bool {
+        self.entries.iter().any(|e| matches!(e.operation, Operation::Rename { .. }))
+    }
+}
+
+impl Journal {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Journal { path: path.into() }
+    }
+
+    /// Journal dans le dossier de données utilisateur (ex: `~/.local/share/TimeShotRenamer/journal.jsonl`).
+    pub fn open_default() -> io::Result<Self> {
+        let data_dir = dirs::data_dir().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "Dossier de données utilisateur introuvable")
+        })?;
+        Ok(Journal::new(data_dir.join("TimeShotRenamer").join(JOURNAL_FILE_NAME)))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Corbeille de l'application, à côté du fichier journal.
+    pub fn trash_dir(&self) -> PathBuf {
+        self.path.parent().map(|p| p.join("trash")).unwrap_or_else(|| PathBuf::from("trash"))
+    }
+
+    /// Ajoute les opérations d'un lot au journal, avec l'horodatage courant.
+    pub fn append(&self, batch_id: &str, operations: Vec<Operation>) -> io::Result<()> {
+        if operations.is_empty() {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        let timestamp = Local::now().naive_local();
+        for operation in operations {
+            let entry = JournalEntry { batch_id: batch_id.to_string(), timestamp, operation };
+            let line = serde_json::to_string(&entry)?;
+            writeln!(file, "{}", line)?;
+        }
+        file.flush()
+    }
+
+    /// Lit toutes les entrées, dans l'ordre d'écriture. Les lignes illisibles sont ignorées.
+    pub fn read_all(&self) -> io::Result<Vec<JournalEntry>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<JournalEntry>(&line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => log::warn!("Entrée de journal ignorée ({}): {}", e, line),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Entrées d'un lot donné, dans l'ordre d'écriture.
+    pub fn batch_entries(&self, batch_id: &str) -> io::Result<Vec<JournalEntry>> {
+        Ok(self.read_all()?.into_iter().filter(|e| e.batch_id == batch_id).collect())
+    }
+
+    /// Lots du journal, du plus récent au plus ancien (entrées de chaque lot dans l'ordre d'écriture).
+    pub fn batches(&self) -> io::Result<Vec<JournalBatch>> {
+        let mut batches: Vec<JournalBatch> = Vec::new();
+        let mut index_by_id: HashMap<String, usize> = HashMap::new();
+        for entry in self.read_all()? {
+            match index_by_id.get(&entry.batch_id) {
+                Some(&index) => batches[index].entries.push(entry),
+                None => {
+                    index_by_id.insert(entry.batch_id.clone(), batches.len());
+                    batches.push(JournalBatch { batch_id: entry.batch_id.clone(), timestamp: entry.timestamp, entries: vec![entry] });
+                }
+            }
+        }
+        batches.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| b.batch_id.cmp(&a.batch_id)));
+        Ok(batches)
+    }
+
+    /// Réécrit le journal sans les entrées pour lesquelles `remove` renvoie `true`.
+    pub fn remove_entries<F: Fn(&JournalEntry) -> bool>(&self, remove: F) -> io::Result<()> {
+        let kept: Vec<JournalEntry> = self.read_all()?.into_iter().filter(|e| !remove(e)).collect();
+        let tmp_path = self.path.with_extension("jsonl.tmp");
+        {
+            let mut file = File::create(&tmp_path)?;
+            for entry in &kept {
+                writeln!(file, "{}", serde_json::to_string(entry)?)?;
+            }
+            file.flush()?;
+        }
+        fs::rename(tmp_path, &self.path)
+    }
+}
+
+/// Identifiant de lot basé sur l'heure locale (ex: `20250403-153012-123`).
+pub fn new_batch_id() -> String {
+    Local::now().format("%Y%m%d-%H%M%S-%3f").to_string()
+}
@@ -0,0 +1,79 @@
+// timeshot_core/src/journal/trash.rs
+//
+// Corbeille de l'application : doublons mis à la corbeille et copies de sauvegarde des doublons
+// remplacés par un lien physique. Rien n'y est libéré tant qu'elle n'est pas vidée ; la vider
+// supprime ces fichiers et rend les opérations correspondantes définitives (entrées retirées du journal).
+
+use super::entry::Operation;
+use super::store::Journal;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default)]
+pub struct TrashReport {
+    /// Fichiers supprimés de la corbeille.
+    pub deleted: usize,
+    /// Espace réellement libéré.
+    pub freed_bytes: u64,
+    pub errors: Vec<String>,
+}
+
+/// Fichier conservé dans la corbeille par cette opération.
+fn trashed_file(operation: &Operation) -> Option<&PathBuf> {
+    match operation {
+        Operation::Trash { to, .. } => Some(to),
+        Operation::HardlinkReplace { backup, .. } => Some(backup),
+        _ => None,
+    }
+}
+
+/// Nombre de fichiers et taille occupée par la corbeille (d'après le journal).
+pub fn trash_usage(journal: &Journal) -> Result<(usize, u64), String> {
+    let entries = journal.read_all().map_err(|e| format!("Lecture du journal impossible: {}", e))?;
+    Ok(entries
+        .iter()
+        .filter_map(|entry| trashed_file(&entry.operation))
+        .filter_map(|path| fs::metadata(path).ok())
+        .fold((0, 0), |(count, size), metadata| (count + 1, size + metadata.len())))
+}
+
+/// Vide la corbeille : supprime les fichiers et retire du journal les entrées correspondantes,
+/// qui ne peuvent plus être annulées. Les entrées en échec restent dans le journal.
+pub fn empty_trash(journal: &Journal) -> Result<TrashReport, String> {
+    let entries = journal.read_all().map_err(|e| format!("Lecture du journal impossible: {}", e))?;
+    let mut report = TrashReport::default();
+    let mut purged_paths: Vec<&PathBuf> = Vec::new();
+
+    for entry in &entries {
+        let Some(path) = trashed_file(&entry.operation) else { continue };
+        let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        match fs::remove_file(path) {
+            Ok(()) => { report.deleted += 1; report.freed_bytes += size; purged_paths.push(path); }
+            // Déjà absent : l'entrée n'a plus rien à restaurer
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => purged_paths.push(path),
+            Err(e) => {
+                let error = format!("Suppression de '{}' impossible: {}", path.display(), e);
+                eprintln!("Attention : {}", error);
+                report.errors.push(error);
+            }
+        }
+    }
+
+    journal
+        .remove_entries(|entry| trashed_file(&entry.operation).is_some_and(|path| purged_paths.contains(&path)))
+        .map_err(|e| format!("Mise à jour du journal impossible: {}", e))?;
+    remove_empty_dirs(&journal.trash_dir());
+    Ok(report)
+}
+
+/// Supprime les dossiers vides laissés dans la corbeille (le dossier racine est conservé).
+fn remove_empty_dirs(dir: &Path) {
+    let Ok(read_dir) = fs::read_dir(dir) else { return };
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            remove_empty_dirs(&path);
+            let _ = fs::remove_dir(&path); // Échoue sans conséquence si le dossier n'est pas vide
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// timeshot_core/src/journal/undo.rs
+
+use super::entry::{JournalEntry, Operation};
+use super::store::Journal;
+use crate::exif::writer::write_raw_date_fields;
+use crate::hash::resolve::move_file;
+use crate::renamer::timestamps::apply_file_times;
+use std::fs;
+
+/// Résultat d'une annulation : nombre d'opérations défaites et erreurs rencontrées.
+#[derive(Debug, Default)]
+pub struct UndoReport {
+    pub undone: usize,
+    /// Opérations effectivement défaites (ex: pour remettre les fichiers renommés dans une liste).
+    pub operations: Vec<Operation>,
+    pub errors: Vec<String>,
+}
+
+/// Défait une opération du journal.
+pub fn undo_operation(operation: &Operation) -> Result<(), String> {
+    match operation {
+        Operation::ExifDateWrite { path, previous_date, previous_offset, .. } => {
+            let date = previous_date.as_deref().unwrap_or("");
+            write_raw_date_fields(path, date, previous_offset.as_deref())
+                .map_err(|e| format!("Restauration EXIF impossible pour '{}': {}", path.display(), e))
+        }
+        Operation::XmpSidecarWrite { sidecar_path, previous_content, .. } => {
+            let result = match previous_content {
+                Some(content) => fs::write(sidecar_path, content),
+                None => fs::remove_file(sidecar_path),
+            };
+            result.map_err(|e| format!("Restauration du sidecar '{}' impossible: {}", sidecar_path.display(), e))
+        }
+        Operation::SetFileTimes { path, previous_modified, previous_accessed, .. } => {
+            apply_file_times(path, *previous_modified, *previous_accessed)
+                .map_err(|e| format!("Restauration des dates de '{}' impossible: {}", path.display(), e))
+        }
+        Operation::Rename { from, to } => move_file(to, from)
+            .map_err(|e| format!("Retour au nom '{}' impossible: {}", from.display(), e)),
+        Operation::Quarantine { from, to } | Operation::Trash { from, to } => move_file(to, from)
+            .map_err(|e| format!("Restauration de '{}' impossible: {}", from.display(), e)),
+        Operation::HardlinkReplace { path, backup, .. } => fs::remove_file(path)
+            .and_then(|_| move_file(backup, path))
+            .map_err(|e| format!("Restauration de '{}' impossible: {}", path.display(), e)),
+    }
+}
+
+/// Défait les entrées données (de la plus récente à la plus ancienne) et les retire du journal.
+/// Les entrées en échec restent dans le journal pour pouvoir réessayer.
+pub fn undo_entries(journal: &Journal, entries: &[JournalEntry]) -> Result<UndoReport, String> {
+    let mut report = UndoReport::default();
+    let mut undone: Vec<&JournalEntry> = Vec::new();
+
+    for entry in entries.iter().rev() {
+        match undo_operation(&entry.operation) {
+            Ok(()) => { report.undone += 1; report.operations.push(entry.operation.clone()); undone.push(entry); }
+            Err(e) => { eprintln!("Attention : {}", e); report.errors.push(e); }
+        }
+    }
+
+    journal
+        .remove_entries(|e| undone.iter().any(|u| same_entry(u, e)))
+        .map_err(|e| format!("Mise à jour du journal impossible: {}", e))?;
+    Ok(report)
+}
+
+/// Défait un lot complet.
+pub fn undo_batch(journal: &Journal, batch_id: &str) -> Result<UndoReport, String> {
+    let entries = journal
+        .batch_entries(batch_id)
+        .map_err(|e| format!("Lecture du journal impossible: {}", e))?;
+    if entries.is_empty() {
+        return Err(format!("Aucune opération trouvée pour le lot {}", batch_id));
+    }
+    undo_entries(journal, &entries)
+}
+
+fn same_entry(a: &JournalEntry, b: &JournalEntry) -> bool {
+    a.batch_id == b.batch_id
+        && a.timestamp == b.timestamp
+        && serde_json::to_string(&a.operation).ok() == serde_json::to_string(&b.operation).ok()
+}
@@ -0,0 +1,59 @@
+// timeshot_core/src/renamer/apply.rs
+//
+// Application d'un plan de renommage sur le disque, chaque renommage étant journalisé
+// pour pouvoir être annulé (`journal::undo`).
+
+use super::plan::{target_taken, RenamePlan};
+use crate::journal::entry::Operation;
+use crate::journal::store::{new_batch_id, Journal};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default)]
+pub struct RenameReport {
+    /// Lot du journal (absent si aucun fichier n'a été renommé).
+    pub batch_id: Option<String>,
+    /// Renommages effectués (ancien chemin, nouveau chemin).
+    pub renamed: Vec<(PathBuf, PathBuf)>,
+    /// Opérations non tentées à cause d'un conflit du plan.
+    pub conflicts: usize,
+    pub errors: Vec<String>,
+}
+
+/// Renomme les fichiers du plan (les opérations en conflit sont ignorées et signalées).
+pub fn apply_rename_plan(plan: &RenamePlan, journal: &Journal) -> RenameReport {
+    let batch_id = new_batch_id();
+    let mut report = RenameReport::default();
+
+    for operation in &plan.operations {
+        if let Some(conflict) = &operation.conflict {
+            report.conflicts += 1;
+            report.errors.push(format!("'{}' non renommé : {}", operation.from.display(), conflict));
+            continue;
+        }
+        // Le plan peut dater : fs::rename écraserait sans rien dire un fichier apparu depuis
+        if target_taken(&operation.from, &operation.to) {
+            report.conflicts += 1;
+            report.errors.push(format!("'{}' non renommé : '{}' existe déjà", operation.from.display(), operation.to.display()));
+            continue;
+        }
+        match rename_one(&operation.from, &operation.to, journal, &batch_id) {
+            Ok(()) => report.renamed.push((operation.from.clone(), operation.to.clone())),
+            Err(e) => { eprintln!("Attention : {}", e); report.errors.push(e); }
+        }
+    }
+
+    if !report.renamed.is_empty() { report.batch_id = Some(batch_id); }
+    report
+}
+
+fn rename_one(from: &Path, to: &Path, journal: &Journal, batch_id: &str) -> Result<(), String> {
+    journal
+        .append(batch_id, vec![Operation::Rename { from: from.to_path_buf(), to: to.to_path_buf() }])
+        .map_err(|e| format!("Écriture du journal impossible: {}", e))?;
+    if let Err(e) = fs::rename(from, to) {
+        let _ = journal.remove_entries(|entry| entry.batch_id == batch_id && entry.operation.path() == from);
+        return Err(format!("Erreur renommage '{}': {}", from.display(), e));
+    }
+    Ok(())
+}
@@ -1,22 +1,19 @@
-
-use std::collections::HashMap;
+use crate::renamer::template::{NameCounters, NameTemplate};
 use crate::types::FileAnalysis;
 
 pub fn generate_filename(
     analysis: &FileAnalysis,
-    name_counter: &mut HashMap<String, usize>,
+    template: &NameTemplate,
+    name_counter: &mut NameCounters,
 ) -> String {
-    let date = analysis.exif.date_taken
-        .map(|d| d.format("%Y-%m-%d_%H%M%S").to_string())
-        .unwrap_or_else(|| "unknown".to_string());
-
-    let base_name = analysis.original_name.replace(' ', "_");
-    let folder = analysis.folder_name.replace(' ', "_");
-
-    let key = format!("{date}");
-    let count = name_counter.entry(key.clone()).or_insert(0);
-    let suffix = if *count > 0 { format!("_{:02}", count) } else { "".to_string() };
-    *count += 1;
+    template.render(analysis, name_counter)
+}
 
-    format!("{date}{suffix}_{folder}_{base_name}")
+/// Recalcule les noms proposés avec un autre modèle, sans nouvelle analyse
+/// (les compteurs repartent de zéro et suivent l'ordre de `data`).
+pub fn apply_name_template(data: &mut [FileAnalysis], template: &NameTemplate) {
+    let mut name_counter = NameCounters::default();
+    for analysis in data.iter_mut() {
+        analysis.new_name = Some(generate_filename(analysis, template, &mut name_counter));
+    }
 }
@@ -0,0 +1,217 @@
+// timeshot_core/src/renamer/mapping.rs
+//
+// Import d'un CSV de renommage (export CSV dont la colonne `new_name` a été modifiée,
+// par exemple dans un tableur) et transformation en plan de renommage.
+
+use super::plan::{validate_file_name, RenamePlan};
+use crate::hash::compute::{compute_file_hash_with, HashAlgorithm};
+use crate::types::FileAnalysis;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default)]
+pub struct MappingImport {
+    /// Renommages retenus (avec leurs éventuels conflits).
+    pub plan: RenamePlan,
+    /// Lignes dont le nom est vide ou inchangé.
+    pub unchanged: usize,
+    /// Lignes rejetées (fichier introuvable, hash différent, nom invalide...).
+    pub errors: Vec<String>,
+}
+
+/// Lit le CSV (séparateur ',', ';' ou tabulation) et associe chaque ligne à un fichier analysé :
+/// par la colonne `full_path` si elle existe, sinon par `folder_name` + `original_name`
+/// (qui doivent alors désigner un seul fichier).
+/// Si la colonne `file_hash` est remplie, le fichier est re-haché avec l'algorithme de la colonne
+/// `hash_algorithm` pour vérifier qu'il n'a pas changé. Sans cette colonne, l'algorithme de l'analyse
+/// est utilisé si la longueur du hash lui correspond ; sinon la vérification est impossible et ignorée.
+pub fn import_rename_mapping(csv_path: &Path, files: &[FileAnalysis]) -> Result<MappingImport, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .delimiter(detect_delimiter(csv_path))
+        .from_path(csv_path)
+        .map_err(|e| format!("Ouverture de '{}' impossible: {}", csv_path.display(), e))?;
+    let headers = reader.headers().map_err(|e| format!("En-tête CSV illisible: {}", e))?.clone();
+    let column = |name: &str| headers.iter().position(|h| h.trim() == name);
+    let new_name_col = column("new_name").ok_or("Colonne 'new_name' absente du CSV")?;
+    let full_path_col = column("full_path");
+    let original_name_col = column("original_name");
+    let folder_name_col = column("folder_name");
+    let file_hash_col = column("file_hash");
+    let hash_algorithm_col = column("hash_algorithm");
+    if full_path_col.is_none() && original_name_col.is_none() {
+        return Err("Colonne 'full_path' ou 'original_name' nécessaire pour retrouver les fichiers".to_string());
+    }
+
+    let by_path: HashMap<&Path, &FileAnalysis> = files.iter().map(|f| (f.full_original_path.as_path(), f)).collect();
+    let mut by_name: HashMap<(&str, &str), Vec<&FileAnalysis>> = HashMap::new();
+    for file in files {
+        by_name.entry((file.folder_name.as_str(), file.original_name.as_str())).or_default().push(file);
+    }
+
+    let mut import = MappingImport::default();
+    let mut renames: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for (row_index, record) in reader.records().enumerate() {
+        // Ligne 1 : en-tête
+        let line = row_index + 2;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => { import.errors.push(format!("Ligne {} illisible: {}", line, e)); continue; }
+        };
+        let field = |col: Option<usize>| col.and_then(|c| record.get(c)).map(str::trim).unwrap_or("");
+
+        let file = if !field(full_path_col).is_empty() {
+            by_path.get(Path::new(field(full_path_col))).copied().ok_or_else(|| {
+                format!("Ligne {} : '{}' ne fait pas partie des fichiers analysés", line, field(full_path_col))
+            })
+        } else {
+            match by_name.get(&(field(folder_name_col), field(original_name_col))).map(Vec::as_slice) {
+                Some([file]) => Ok(*file),
+                Some(matches) if matches.len() > 1 => Err(format!(
+                    "Ligne {} : '{}' désigne {} fichiers, ajoutez la colonne 'full_path'",
+                    line, field(original_name_col), matches.len()
+                )),
+                _ => Err(format!("Ligne {} : fichier '{}' introuvable dans l'analyse", line, field(original_name_col))),
+            }
+        };
+        let file = match file {
+            Ok(file) => file,
+            Err(e) => { import.errors.push(e); continue; }
+        };
+
+        let new_name = field(Some(new_name_col));
+        if new_name.is_empty() || new_name == file.original_name {
+            import.unchanged += 1;
+            continue;
+        }
+        if let Err(e) = check_row(file, new_name, field(file_hash_col), field(hash_algorithm_col)) {
+            import.errors.push(format!("Ligne {} : {}", line, e));
+            continue;
+        }
+        let parent = file.full_original_path.parent().map(Path::to_path_buf).unwrap_or_default();
+        renames.push((file.full_original_path.clone(), parent.join(new_name)));
+    }
+
+    import.plan = RenamePlan::new(renames);
+    Ok(import)
+}
+
+/// Séparateur d'après la première ligne : ';' ou tabulation s'ils sont plus fréquents que ','
+/// (hors valeurs entre guillemets).
+fn detect_delimiter(csv_path: &Path) -> u8 {
+    let first_line = File::open(csv_path)
+        .ok()
+        .and_then(|file| BufReader::new(file).lines().next())
+        .and_then(Result::ok)
+        .unwrap_or_default();
+    let mut in_quotes = false;
+    let unquoted: String = first_line
+        .chars()
+        .filter(|&c| { if c == '"' { in_quotes = !in_quotes; } !in_quotes })
+        .collect();
+    let count = |d: char| unquoted.matches(d).count();
+    [b';', b'\t']
+        .into_iter()
+        .map(|d| (d, count(d as char)))
+        .filter(|&(_, n)| n > count(','))
+        .max_by_key(|&(_, n)| n)
+        .map(|(d, _)| d)
+        .unwrap_or(b',')
+}
+
+fn check_row(file: &FileAnalysis, new_name: &str, expected_hash: &str, algorithm_name: &str) -> Result<(), String> {
+    validate_file_name(new_name)?;
+    let path = &file.full_original_path;
+    if !path.is_file() {
+        return Err(format!("'{}' n'existe plus", path.display()));
+    }
+    if expected_hash.is_empty() {
+        return Ok(());
+    }
+    let algorithm = if algorithm_name.is_empty() {
+        file.hash_algorithm
+    } else {
+        HashAlgorithm::from_name(algorithm_name).ok_or_else(|| format!("Algorithme de hash inconnu : '{}'", algorithm_name))?
+    };
+    // Hash d'un autre algorithme (colonne absente) : rien à comparer
+    if algorithm.hex_len() != expected_hash.len() {
+        return Ok(());
+    }
+    match compute_file_hash_with(path, algorithm) {
+        Ok(hash) if hash.eq_ignore_ascii_case(expected_hash) => Ok(()),
+        Ok(_) => Err(format!("'{}' a été modifié depuis l'export (hash différent)", path.display())),
+        Err(e) => Err(format!("'{}' illisible: {}", path.display(), e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::store::Journal;
+    use crate::renamer::apply::apply_rename_plan;
+    use crate::test_support::{file_analysis, test_dir};
+    use std::fs;
+
+    fn delimiter_of(name: &str, content: &str) -> u8 {
+        let path = std::env::temp_dir().join(format!("timeshot_mapping_{}_{}.csv", std::process::id(), name));
+        fs::write(&path, content).unwrap();
+        let delimiter = detect_delimiter(&path);
+        let _ = fs::remove_file(&path);
+        delimiter
+    }
+
+    #[test]
+    fn detects_delimiter_from_header() {
+        assert_eq!(delimiter_of("comma", "full_path,original_name,new_name\n/a,b;c,d\n"), b',');
+        assert_eq!(delimiter_of("semicolon", "full_path;original_name;new_name\n/a;b,c;d\n"), b';');
+        assert_eq!(delimiter_of("tab", "full_path\toriginal_name\tnew_name\n"), b'\t');
+        // Virgules entre guillemets ignorées (tableur français)
+        assert_eq!(delimiter_of("quoted", "\"Nom, complet\";\"Date, heure\";new_name\n"), b';');
+        // Égalité ou ligne sans séparateur : virgule par défaut
+        assert_eq!(delimiter_of("tie", "a,b;c\n"), b',');
+        assert_eq!(delimiter_of("single", "new_name\n"), b',');
+        assert_eq!(delimiter_of("empty", ""), b',');
+    }
+
+    #[test]
+    fn imports_rows_by_path_or_folder_and_name_then_applies_them() {
+        let dir = test_dir("mapping_import");
+        for folder in ["2023", "2024"] { fs::create_dir(dir.join(folder)).unwrap(); }
+        for (path, content) in [("2023/a.jpg", "a"), ("2023/b.jpg", "b"), ("2024/b.jpg", "b2"), ("2024/c.jpg", "c"), ("2024/d.jpg", "d")] {
+            fs::write(dir.join(path), content).unwrap();
+        }
+        // Analyse en BLAKE3, export relu en SHA-256 : la colonne hash_algorithm fait foi
+        let analysis = |path: &str| FileAnalysis {
+            file_hash: Some(compute_file_hash_with(&dir.join(path), HashAlgorithm::Blake3).unwrap()),
+            ..file_analysis(dir.join(path))
+        };
+        let files: Vec<FileAnalysis> = ["2023/a.jpg", "2023/b.jpg", "2024/b.jpg", "2024/c.jpg", "2024/d.jpg"].into_iter().map(analysis).collect();
+        let sha256 = |path: &str| compute_file_hash_with(&dir.join(path), HashAlgorithm::Sha256).unwrap();
+        let csv = [
+            "full_path;folder_name;original_name;file_hash;hash_algorithm;new_name".to_string(),
+            format!("{};2023;a.jpg;{};sha256;vacances.jpg", dir.join("2023/a.jpg").display(), sha256("2023/a.jpg")),
+            format!(";2024;b.jpg;{};;plage.jpg", files[2].file_hash.as_deref().unwrap()),
+            format!(";2024;c.jpg;{};sha256;modifié.jpg", sha256("2024/d.jpg")),
+            ";;b.jpg;;;ambigu.jpg".to_string(),
+            ";2024;d.jpg;;;d.jpg".to_string(),
+            ";2024;absent.jpg;;;x.jpg".to_string(),
+        ];
+        let csv_path = dir.join("plan.csv");
+        fs::write(&csv_path, csv.join("\n")).unwrap();
+
+        let import = import_rename_mapping(&csv_path, &files).unwrap();
+        assert_eq!(import.unchanged, 1);
+        assert_eq!(import.errors.len(), 3, "{:?}", import.errors);
+        assert!(import.errors[0].starts_with("Ligne 4") && import.errors[0].contains("hash différent"));
+        assert!(import.errors[1].starts_with("Ligne 5") && import.errors[1].contains("introuvable"));
+        assert!(import.errors[2].starts_with("Ligne 7"));
+        assert!(!import.plan.has_conflicts());
+
+        let report = apply_rename_plan(&import.plan, &Journal::new(dir.join("journal.jsonl")));
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert!(dir.join("2023/vacances.jpg").is_file() && dir.join("2024/plage.jpg").is_file());
+        assert!(dir.join("2023/b.jpg").is_file() && dir.join("2024/c.jpg").is_file());
+    }
+}
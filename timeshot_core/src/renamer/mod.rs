@@ -1,2 +1,7 @@
+pub mod apply;
 pub mod generator;
-pub mod rules;
\ No newline at end of file
+pub mod mapping;
+pub mod plan;
+pub mod rules;
+pub mod template;
+pub mod timestamps;
\ No newline at end of file
@@ -0,0 +1,206 @@
+// timeshot_core/src/renamer/plan.rs
+//
+// Plan de renommage : liste des opérations (ancien -> nouveau chemin) avec détection des conflits
+// avant toute modification du disque.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Longueur maximale d'un nom de fichier (octets) sur la plupart des systèmes de fichiers.
+pub const MAX_FILE_NAME_LEN: usize = 255;
+
+/// Au-delà de cette longueur de chemin (caractères), certains outils (Windows notamment) échouent.
+pub const LONG_PATH_WARNING_LEN: usize = 260;
+
+pub(crate) const FORBIDDEN_CHARS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Noms de périphériques réservés sous Windows, avec ou sans extension (ex: "CON", "nul.txt").
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+#[derive(Debug, Clone)]
+pub struct RenameOperation {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    /// Raison pour laquelle l'opération ne peut pas être appliquée telle quelle.
+    pub conflict: Option<String>,
+}
+
+impl RenameOperation {
+    /// Avertissement non bloquant : chemin cible très long.
+    pub fn warning(&self) -> Option<String> {
+        let length = self.to.to_string_lossy().chars().count();
+        (length > LONG_PATH_WARNING_LEN).then(|| format!("Chemin long ({} caractères, plus de {})", length, LONG_PATH_WARNING_LEN))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RenamePlan {
+    pub operations: Vec<RenameOperation>,
+}
+
+impl RenamePlan {
+    /// Construit le plan et vérifie les conflits : nom invalide, cible commune à plusieurs fichiers,
+    /// cible déjà présente sur le disque.
+    pub fn new(renames: Vec<(PathBuf, PathBuf)>) -> Self {
+        let mut target_count: HashMap<PathBuf, usize> = HashMap::new();
+        for (_, to) in &renames {
+            *target_count.entry(to.clone()).or_insert(0) += 1;
+        }
+        let operations = renames
+            .into_iter()
+            .map(|(from, to)| {
+                let conflict = find_conflict(&from, &to, target_count[&to]);
+                RenameOperation { from, to, conflict }
+            })
+            .collect();
+        RenamePlan { operations }
+    }
+
+    pub fn has_conflicts(&self) -> bool {
+        self.operations.iter().any(|op| op.conflict.is_some())
+    }
+
+    pub fn conflicts(&self) -> impl Iterator<Item = &RenameOperation> {
+        self.operations.iter().filter(|op| op.conflict.is_some())
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &RenameOperation> {
+        self.operations.iter().filter(|op| op.conflict.is_none() && op.warning().is_some())
+    }
+}
+
+/// Vérifie qu'un nom de fichier (sans dossier) est utilisable sur les systèmes courants.
+pub fn validate_file_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Nom vide".to_string());
+    }
+    let stem = name.split('.').next().unwrap_or_default().trim_end();
+    if name == "." || name == ".." || RESERVED_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(stem)) {
+        return Err(format!("Nom réservé : {}", name));
+    }
+    if let Some(c) = name.chars().find(|c| FORBIDDEN_CHARS.contains(c) || c.is_control()) {
+        return Err(format!("Caractère interdit '{}' dans '{}'", c.escape_default(), name));
+    }
+    if name.ends_with(' ') || name.ends_with('.') {
+        return Err(format!("Nom terminé par un espace ou un point : '{}'", name));
+    }
+    if name.len() > MAX_FILE_NAME_LEN {
+        return Err(format!("Nom trop long ({} octets, maximum {})", name.len(), MAX_FILE_NAME_LEN));
+    }
+    Ok(())
+}
+
+fn find_conflict(from: &Path, to: &Path, same_target_count: usize) -> Option<String> {
+    let name = to.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    if let Err(e) = validate_file_name(&name) {
+        return Some(e);
+    }
+    if same_target_count > 1 {
+        return Some(format!("{} fichiers renommés en '{}'", same_target_count, name));
+    }
+    if target_taken(from, to) {
+        return Some(format!("'{}' existe déjà", to.display()));
+    }
+    None
+}
+
+/// La cible existe déjà sur le disque. Un changement de casse seule n'est pas un conflit si la cible
+/// désigne le fichier lui-même (système insensible à la casse), mais l'est si c'est un autre fichier.
+pub(crate) fn target_taken(from: &Path, to: &Path) -> bool {
+    let case_only = from.parent() == to.parent()
+        && from.file_name().map(|n| n.to_string_lossy().to_lowercase()) == to.file_name().map(|n| n.to_string_lossy().to_lowercase());
+    to.exists() && !(case_only && same_file(from, to))
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        matches!((fs::metadata(a), fs::metadata(b)), (Ok(a), Ok(b)) if a.dev() == b.dev() && a.ino() == b.ino())
+    }
+    #[cfg(not(unix))]
+    {
+        matches!((fs::canonicalize(a), fs::canonicalize(b)), (Ok(a), Ok(b)) if a == b)
+    }
+}
+
+/// Parties communes de deux noms : longueurs (octets) du préfixe et du suffixe identiques,
+/// pour mettre en évidence la partie modifiée.
+pub fn common_affixes(old: &str, new: &str) -> (usize, usize) {
+    let prefix: usize = old.chars().zip(new.chars()).take_while(|(a, b)| a == b).map(|(c, _)| c.len_utf8()).sum();
+    // Le suffixe est cherché après le préfixe : les deux parties ne se chevauchent pas
+    let suffix: usize = old[prefix..].chars().rev().zip(new[prefix..].chars().rev()).take_while(|(a, b)| a == b).map(|(c, _)| c.len_utf8()).sum();
+    (prefix, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_dir;
+
+    #[test]
+    fn rejects_unusable_file_names() {
+        for name in ["", "  ", ".", "..", "a/b.jpg", "a:b.jpg", "a\nb.jpg", "CON", "nul.txt", "Com1.jpg", "lpt9", "photo ", "photo.", "photo.jpg."] {
+            assert!(validate_file_name(name).is_err(), "{:?}", name);
+        }
+        for name in ["photo.jpg", ".cache", "CONSOLE.jpg", "con_1.jpg", "é 2023.jpg"] {
+            assert!(validate_file_name(name).is_ok(), "{:?}", name);
+        }
+        assert!(validate_file_name(&"a".repeat(MAX_FILE_NAME_LEN)).is_ok());
+        assert!(validate_file_name(&"a".repeat(MAX_FILE_NAME_LEN + 1)).unwrap_err().contains("trop long"));
+        // Limite en octets : 128 caractères de 2 octets dépassent 255
+        assert!(validate_file_name(&"é".repeat(128)).is_err());
+    }
+
+    #[test]
+    fn flags_shared_targets_and_existing_files() {
+        let dir = test_dir("plan_conflicts");
+        for name in ["a.jpg", "b.jpg", "c.jpg", "pris.jpg"] { fs::write(dir.join(name), name).unwrap(); }
+        let plan = RenamePlan::new(vec![
+            (dir.join("a.jpg"), dir.join("même.jpg")),
+            (dir.join("b.jpg"), dir.join("même.jpg")),
+            (dir.join("c.jpg"), dir.join("pris.jpg")),
+            (dir.join("pris.jpg"), dir.join("libre.jpg")),
+        ]);
+        let conflicts: Vec<Option<&str>> = plan.operations.iter().map(|op| op.conflict.as_deref()).collect();
+        assert!(conflicts[0].unwrap().contains("2 fichiers"));
+        assert!(conflicts[1].unwrap().contains("2 fichiers"));
+        assert!(conflicts[2].unwrap().contains("existe déjà"));
+        assert_eq!(conflicts[3], None);
+        assert_eq!(plan.conflicts().count(), 3);
+    }
+
+    #[test]
+    fn case_only_rename_is_not_a_conflict_unless_another_file_exists() {
+        let dir = test_dir("plan_case_only");
+        fs::write(dir.join("img.jpg"), "a").unwrap();
+        let plan = RenamePlan::new(vec![(dir.join("img.jpg"), dir.join("IMG.jpg"))]);
+        assert!(!plan.has_conflicts());
+
+        // Système sensible à la casse : "IMG.jpg" peut être un autre fichier
+        fs::write(dir.join("IMG.jpg"), "b").unwrap();
+        if fs::read(dir.join("img.jpg")).unwrap() == b"a" {
+            let plan = RenamePlan::new(vec![(dir.join("img.jpg"), dir.join("IMG.jpg"))]);
+            assert!(plan.has_conflicts());
+        }
+    }
+
+    #[test]
+    fn common_affixes_respect_multibyte_characters() {
+        assert_eq!(common_affixes("photo.jpg", "photo.jpg"), (9, 0));
+        assert_eq!(common_affixes("IMG_1.jpg", "2023_IMG_1.jpg"), (0, 9));
+        assert_eq!(common_affixes("été_1.jpg", "été_2.jpg"), ("été_".len(), ".jpg".len()));
+        // "é" et "è" partagent leur premier octet : la coupure reste sur une limite de caractère
+        let (old, new) = ("café.jpg", "cafè.jpg");
+        let (prefix, suffix) = common_affixes(old, new);
+        assert_eq!((prefix, suffix), (3, 4));
+        assert!(old.is_char_boundary(prefix) && new.is_char_boundary(new.len() - suffix));
+        // Préfixe et suffixe ne se chevauchent pas ("aa" -> "aaa")
+        let (prefix, suffix) = common_affixes("aa", "aaa");
+        assert!(prefix + suffix <= 2);
+    }
+}
@@ -0,0 +1,323 @@
+// timeshot_core/src/renamer/template.rs
+//
+// Modèles de nommage : texte libre et jetons entre accolades, ex: "{date}{suffix}_{folder}_{name}".
+
+use crate::renamer::plan::FORBIDDEN_CHARS;
+use crate::types::FileAnalysis;
+use chrono::format::{Item, StrftimeItems};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Modèle reproduisant le nommage historique : date, suffixe anti-collision, dossier, nom d'origine.
+pub const DEFAULT_TEMPLATE: &str = "{date}{suffix}_{folder}_{name}";
+
+/// Jetons disponibles, avec leur description (aide et boutons d'insertion).
+pub const TEMPLATE_TOKENS: [(&str, &str); 16] = [
+    ("{date}", "Date de prise de vue (AAAA-MM-JJ_HHMMSS)"),
+    ("{date:%Y%m%d}", "Date de prise de vue, format strftime au choix"),
+    ("{year}", "Année"),
+    ("{month}", "Mois"),
+    ("{day}", "Jour"),
+    ("{hour}", "Heure"),
+    ("{minute}", "Minute"),
+    ("{second}", "Seconde"),
+    ("{camera}", "Modèle de l'appareil"),
+    ("{lens}", "Objectif"),
+    ("{folder}", "Dossier parent"),
+    ("{name}", "Nom d'origine avec extension"),
+    ("{stem}", "Nom d'origine sans extension"),
+    ("{ext}", "Extension d'origine"),
+    ("{counter:3}", "Compteur séquentiel (nombre de chiffres)"),
+    ("{exif:Make}", "Valeur d'un tag EXIF"),
+];
+
+/// Valeur utilisée quand l'information manque (date, appareil, tag absent).
+const UNKNOWN: &str = "unknown";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Date(String),
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Camera,
+    Lens,
+    Folder,
+    Name,
+    Stem,
+    Ext,
+    /// Compteur séquentiel sur l'ensemble des fichiers, sur `width` chiffres.
+    Counter(usize),
+    /// "_01", "_02"... quand plusieurs fichiers ont la même date.
+    Suffix,
+    Exif(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Text(String),
+    Token(Token),
+}
+
+/// Compteurs partagés par les fichiers d'une même génération de noms.
+#[derive(Debug, Clone, Default)]
+pub struct NameCounters {
+    /// Nombre de fichiers déjà nommés par date (pour `{suffix}`).
+    by_date: HashMap<String, usize>,
+    /// Nombre de fichiers déjà nommés (pour `{counter}`).
+    sequence: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameTemplate {
+    parts: Vec<Part>,
+    /// Le modèle contient `{name}` ou `{ext}` : l'extension d'origine n'est pas ajoutée automatiquement.
+    has_extension: bool,
+}
+
+impl Default for NameTemplate {
+    fn default() -> Self {
+        NameTemplate::parse(DEFAULT_TEMPLATE).expect("modèle par défaut valide")
+    }
+}
+
+impl NameTemplate {
+    /// Analyse un modèle. Erreur si une accolade n'est pas fermée, si un jeton est inconnu,
+    /// si un format de date est invalide ou si le texte contient un caractère interdit.
+    pub fn parse(template: &str) -> Result<Self, String> {
+        if template.trim().is_empty() {
+            return Err("Modèle vide".to_string());
+        }
+        let mut parts: Vec<Part> = Vec::new();
+        let mut rest = template;
+        while !rest.is_empty() {
+            match rest.find(['{', '}']) {
+                None => { parts.push(Part::Text(rest.to_string())); break; }
+                Some(index) if rest[index..].starts_with('}') => return Err(format!("Accolade fermante sans ouverture en position {}", template.len() - rest.len() + index + 1)),
+                Some(index) => {
+                    if index > 0 { parts.push(Part::Text(rest[..index].to_string())); }
+                    let Some(end) = rest[index..].find('}') else { return Err(format!("Jeton non fermé : '{}'", &rest[index..])) };
+                    parts.push(Part::Token(parse_token(&rest[index + 1..index + end])?));
+                    rest = &rest[index + end + 1..];
+                }
+            }
+        }
+        for part in &parts {
+            if let Part::Text(text) = part {
+                if let Some(c) = text.chars().find(|c| FORBIDDEN_CHARS.contains(c)) {
+                    return Err(format!("Caractère interdit dans un nom de fichier : '{}'", c));
+                }
+            }
+        }
+        let has_extension = parts.iter().any(|p| matches!(p, Part::Token(Token::Name) | Part::Token(Token::Ext)));
+        Ok(NameTemplate { parts, has_extension })
+    }
+
+    /// Nom de fichier produit pour ce fichier. Les valeurs insérées ne contiennent ni espace ni caractère
+    /// interdit ; l'extension d'origine est ajoutée si le modèle ne contient ni `{name}` ni `{ext}`.
+    pub fn render(&self, analysis: &FileAnalysis, counters: &mut NameCounters) -> String {
+        let date = analysis.exif.date_taken;
+        let default_date = date.map(|d| d.format("%Y-%m-%d_%H%M%S").to_string()).unwrap_or_else(|| UNKNOWN.to_string());
+        let path = Path::new(&analysis.original_name);
+        let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+        let extension = path.extension().map(|e| e.to_string_lossy().into_owned());
+
+        let duplicates = counters.by_date.entry(default_date.clone()).or_insert(0);
+        let suffix = if *duplicates > 0 { format!("_{:02}", duplicates) } else { String::new() };
+        *duplicates += 1;
+        counters.sequence += 1;
+
+        let date_part = |format: &str| date.map(|d| d.format(format).to_string()).unwrap_or_else(|| UNKNOWN.to_string());
+        let mut name = String::new();
+        for part in &self.parts {
+            let value = match part {
+                Part::Text(text) => { name.push_str(text); continue; }
+                Part::Token(Token::Date(format)) if format.is_empty() => default_date.clone(),
+                Part::Token(Token::Date(format)) => date_part(format),
+                Part::Token(Token::Year) => date_part("%Y"),
+                Part::Token(Token::Month) => date_part("%m"),
+                Part::Token(Token::Day) => date_part("%d"),
+                Part::Token(Token::Hour) => date_part("%H"),
+                Part::Token(Token::Minute) => date_part("%M"),
+                Part::Token(Token::Second) => date_part("%S"),
+                Part::Token(Token::Camera) => analysis.exif.camera_model.clone().unwrap_or_else(|| UNKNOWN.to_string()),
+                Part::Token(Token::Lens) => analysis.exif.lens_model.clone().unwrap_or_else(|| UNKNOWN.to_string()),
+                Part::Token(Token::Folder) => analysis.folder_name.clone(),
+                Part::Token(Token::Name) => analysis.original_name.clone(),
+                Part::Token(Token::Stem) => stem.clone(),
+                // Fichier sans extension : pas de point final ("{stem}.{ext}" -> "README")
+                Part::Token(Token::Ext) if extension.is_none() => { if name.ends_with('.') { name.pop(); } continue; }
+                Part::Token(Token::Ext) => extension.clone().unwrap_or_default(),
+                Part::Token(Token::Counter(width)) => format!("{:0width$}", counters.sequence, width = *width),
+                Part::Token(Token::Suffix) => suffix.clone(),
+                Part::Token(Token::Exif(tag)) => exif_value(analysis, tag).unwrap_or_else(|| UNKNOWN.to_string()),
+            };
+            name.push_str(&sanitize(value.trim()));
+        }
+        match extension {
+            Some(extension) if !self.has_extension => format!("{}.{}", name, extension),
+            _ => name,
+        }
+    }
+}
+
+fn parse_token(token: &str) -> Result<Token, String> {
+    let (name, argument) = match token.split_once(':') {
+        Some((name, argument)) => (name, Some(argument)),
+        None => (token, None),
+    };
+    let token = match (name, argument) {
+        ("date", None) => Token::Date(String::new()),
+        ("date", Some(format)) => {
+            if format.is_empty() || StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                return Err(format!("Format de date invalide : '{}'", format));
+            }
+            Token::Date(format.to_string())
+        }
+        ("year", None) => Token::Year,
+        ("month", None) => Token::Month,
+        ("day", None) => Token::Day,
+        ("hour", None) => Token::Hour,
+        ("minute", None) => Token::Minute,
+        ("second", None) => Token::Second,
+        ("camera", None) => Token::Camera,
+        ("lens", None) => Token::Lens,
+        ("folder", None) => Token::Folder,
+        ("name", None) => Token::Name,
+        ("stem", None) => Token::Stem,
+        ("ext", None) => Token::Ext,
+        ("suffix", None) => Token::Suffix,
+        ("counter", None) => Token::Counter(3),
+        ("counter", Some(width)) => match width.parse::<usize>() {
+            Ok(width) if (1..=9).contains(&width) => Token::Counter(width),
+            _ => return Err(format!("Largeur de compteur invalide : '{}' (1 à 9)", width)),
+        },
+        ("exif", Some(tag)) if !tag.is_empty() => Token::Exif(tag.to_string()),
+        _ => return Err(format!("Jeton inconnu : '{{{}}}'", token)),
+    };
+    Ok(token)
+}
+
+/// Valeur d'un tag EXIF par son nom (ex: "Make", "ExposureTime").
+fn exif_value(analysis: &FileAnalysis, tag: &str) -> Option<String> {
+    let exif = &analysis.exif;
+    match tag {
+        "Model" => exif.camera_model.clone(),
+        "LensModel" => exif.lens_model.clone(),
+        "Artist" => exif.artist.clone(),
+        "ImageDescription" => exif.description.clone(),
+        _ => {
+            let mut matches: Vec<(&String, &String)> = exif.other_fields.iter().filter(|(key, _)| key.split(" (").next() == Some(tag)).collect();
+            // Plusieurs IFD possibles : l'ordre des clés rend le résultat stable (IFD0 avant IFD1)
+            matches.sort();
+            matches.first().map(|(_, value)| value.to_string())
+        }
+    }
+}
+
+fn sanitize(value: &str) -> String {
+    value.chars().map(|c| if c == ' ' || FORBIDDEN_CHARS.contains(&c) { '_' } else { c }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::file_analysis;
+    use crate::types::ExifData;
+    use chrono::NaiveDate;
+
+    fn analysis(original_name: &str, folder_name: &str, date: Option<(u32, u32)>) -> FileAnalysis {
+        let date_taken = date.map(|(day, second)| NaiveDate::from_ymd_opt(2023, 7, day).unwrap().and_hms_opt(14, 30, second).unwrap());
+        let exif = ExifData { date_taken, camera_model: Some("Canon EOS R6".to_string()), ..Default::default() };
+        FileAnalysis { exif, ..file_analysis(Path::new(folder_name).join(original_name)) }
+    }
+
+    fn render_all(template: &str, analyses: &[FileAnalysis]) -> Vec<String> {
+        let template = NameTemplate::parse(template).unwrap();
+        let mut counters = NameCounters::default();
+        analyses.iter().map(|a| template.render(a, &mut counters)).collect()
+    }
+
+    /// Nommage d'avant les modèles (`generate_filename` historique).
+    fn legacy_name(analysis: &FileAnalysis, name_counter: &mut HashMap<String, usize>) -> String {
+        let date = analysis.exif.date_taken.map(|d| d.format("%Y-%m-%d_%H%M%S").to_string()).unwrap_or_else(|| "unknown".to_string());
+        let base_name = analysis.original_name.replace(' ', "_");
+        let folder = analysis.folder_name.replace(' ', "_");
+        let count = name_counter.entry(date.clone()).or_insert(0);
+        let suffix = if *count > 0 { format!("_{:02}", count) } else { "".to_string() };
+        *count += 1;
+        format!("{date}{suffix}_{folder}_{base_name}")
+    }
+
+    #[test]
+    fn rejects_unclosed_and_unknown_tokens() {
+        assert!(NameTemplate::parse("{date").unwrap_err().contains("non fermé"));
+        assert!(NameTemplate::parse("{date}_{name").unwrap_err().contains("non fermé"));
+        assert!(NameTemplate::parse("date}").unwrap_err().contains("sans ouverture"));
+        assert!(NameTemplate::parse("{foo}").unwrap_err().contains("inconnu"));
+        assert!(NameTemplate::parse("{year:2}").unwrap_err().contains("inconnu"));
+        assert!(NameTemplate::parse("{exif:}").unwrap_err().contains("inconnu"));
+        assert!(NameTemplate::parse("{counter:0}").is_err());
+        assert!(NameTemplate::parse("{counter:x}").is_err());
+        assert!(NameTemplate::parse("  ").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_date_format() {
+        assert!(NameTemplate::parse("{date:%Q}").unwrap_err().contains("Format de date invalide"));
+        assert!(NameTemplate::parse("{date:}").is_err());
+        assert!(NameTemplate::parse("{date:%Y%m%d}").is_ok());
+    }
+
+    #[test]
+    fn rejects_forbidden_characters_in_text() {
+        for template in ["{date}/{name}", "{date}:{name}", "a*b", "{stem}?"] {
+            assert!(NameTemplate::parse(template).unwrap_err().contains("Caractère interdit"), "{}", template);
+        }
+        // Les valeurs insérées sont nettoyées, pas rejetées
+        let file = FileAnalysis { exif: ExifData { camera_model: Some("A/B: C".to_string()), ..Default::default() }, ..analysis("a.jpg", "d", None) };
+        assert_eq!(render_all("{camera}", &[file]), vec!["A_B__C.jpg"]);
+    }
+
+    #[test]
+    fn suffix_and_counter_follow_file_order() {
+        let files = [
+            analysis("a.jpg", "d", Some((1, 0))),
+            analysis("b.jpg", "d", Some((1, 0))),
+            analysis("c.jpg", "d", Some((2, 0))),
+            analysis("d.jpg", "d", Some((1, 0))),
+            analysis("e.jpg", "d", None),
+        ];
+        assert_eq!(
+            render_all("{date:%d}{suffix}-{counter:2}", &files),
+            vec!["01-01.jpg", "01_01-02.jpg", "02-03.jpg", "01_02-04.jpg", "unknown-05.jpg"]
+        );
+    }
+
+    #[test]
+    fn adds_original_extension_unless_template_has_one() {
+        let files = [analysis("IMG 1.CR2", "d", Some((1, 0))), analysis("README", "d", Some((2, 0)))];
+        assert_eq!(render_all("{date}", &files), vec!["2023-07-01_143000.CR2", "2023-07-02_143000"]);
+        assert_eq!(render_all("{stem}.{ext}", &files), vec!["IMG_1.CR2", "README"]);
+        assert_eq!(render_all("{date}_{name}", &files), vec!["2023-07-01_143000_IMG_1.CR2", "2023-07-02_143000_README"]);
+    }
+
+    #[test]
+    fn default_template_matches_legacy_naming() {
+        let files = [
+            analysis("IMG_0001.JPG", "Vacances 2023", Some((1, 0))),
+            analysis("IMG 0002.JPG", "Vacances 2023", Some((1, 0))),
+            analysis("photo.png", "Vacances 2023", Some((1, 0))),
+            analysis("scan.tif", "Divers", None),
+            analysis("scan 2.tif", "Divers", None),
+            analysis("IMG_0003.CR2", "Vacances 2023", Some((3, 12))),
+        ];
+        let mut legacy_counter = HashMap::new();
+        let expected: Vec<String> = files.iter().map(|f| legacy_name(f, &mut legacy_counter)).collect();
+        assert_eq!(render_all(DEFAULT_TEMPLATE, &files), expected);
+        assert_eq!(NameTemplate::default(), NameTemplate::parse(DEFAULT_TEMPLATE).unwrap());
+    }
+}
@@ -0,0 +1,131 @@
+// timeshot_core/src/renamer/timestamps.rs
+//
+// Alignement des dates du système de fichiers (mtime/atime) sur la date de prise de vue.
+
+use crate::journal::entry::Operation;
+use crate::journal::store::{new_batch_id, Journal};
+use chrono::{FixedOffset, Local, NaiveDateTime, TimeZone};
+use std::fs::{File, FileTimes};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+#[derive(Debug, Default)]
+pub struct FileTimesReport {
+    /// Lot du journal (absent si aucun fichier n'a été modifié).
+    pub batch_id: Option<String>,
+    pub updated: usize,
+    /// Fichiers déjà à la bonne date.
+    pub skipped: usize,
+    pub errors: Vec<String>,
+}
+
+/// Fichier à dater : date de prise de vue et son décalage horaire EXIF (`ExifData::date_taken_offset`).
+pub type FileTimesTarget = (PathBuf, NaiveDateTime, Option<FixedOffset>);
+
+/// Applique à chaque fichier sa date de prise de vue comme date de modification et d'accès, dans son
+/// fuseau d'origine si le décalage est connu, sinon en heure locale.
+/// Les dates précédentes sont enregistrées dans le journal pour pouvoir revenir en arrière.
+pub fn set_file_times_from_dates(targets: &[FileTimesTarget], journal: &Journal) -> FileTimesReport {
+    set_file_times_in_batch(targets, journal, &new_batch_id())
+}
+
+/// Comme `set_file_times_from_dates`, dans un lot existant (ex: celui du renommage, annulé d'un bloc).
+pub fn set_file_times_in_batch(targets: &[FileTimesTarget], journal: &Journal, batch_id: &str) -> FileTimesReport {
+    let mut report = FileTimesReport::default();
+
+    for (path, date_taken, offset) in targets {
+        match set_one(path, *date_taken, *offset, journal, batch_id) {
+            Ok(true) => report.updated += 1,
+            Ok(false) => report.skipped += 1,
+            Err(e) => { eprintln!("Attention : {}", e); report.errors.push(e); }
+        }
+    }
+
+    if report.updated > 0 { report.batch_id = Some(batch_id.to_string()); }
+    report
+}
+
+/// Remplace les dates de modification et d'accès d'un fichier.
+pub fn apply_file_times(path: &Path, modified: SystemTime, accessed: SystemTime) -> io::Result<()> {
+    let file = File::options().write(true).open(path)?;
+    file.set_times(FileTimes::new().set_modified(modified).set_accessed(accessed))
+}
+
+fn set_one(path: &Path, date_taken: NaiveDateTime, offset: Option<FixedOffset>, journal: &Journal, batch_id: &str) -> Result<bool, String> {
+    let new_time = capture_time(date_taken, offset)
+        .ok_or_else(|| format!("Date {} invalide dans le fuseau local pour '{}'", date_taken, path.display()))?;
+    let metadata = path
+        .metadata()
+        .map_err(|e| format!("Lecture des dates de '{}' impossible: {}", path.display(), e))?;
+    let previous_modified = metadata.modified().map_err(|e| e.to_string())?;
+    let previous_accessed = metadata.accessed().unwrap_or(previous_modified);
+    if previous_modified == new_time {
+        return Ok(false);
+    }
+
+    journal
+        .append(batch_id, vec![Operation::SetFileTimes { path: path.to_path_buf(), previous_modified, previous_accessed, new_time }])
+        .map_err(|e| format!("Écriture du journal impossible: {}", e))?;
+    if let Err(e) = apply_file_times(path, new_time, new_time) {
+        let _ = journal.remove_entries(|entry| entry.batch_id == batch_id && entry.operation.path() == path);
+        return Err(format!("Modification des dates de '{}' impossible: {}", path.display(), e));
+    }
+    Ok(true)
+}
+
+/// Instant de la prise de vue : avec le décalage EXIF s'il existe, sinon dans le fuseau local.
+fn capture_time(date: NaiveDateTime, offset: Option<FixedOffset>) -> Option<SystemTime> {
+    match offset {
+        Some(offset) => offset.from_local_datetime(&date).single().map(SystemTime::from),
+        None => Local.from_local_datetime(&date).earliest().map(SystemTime::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::undo::undo_operation;
+    use crate::test_support::test_dir;
+    use crate::types::ExifData;
+    use chrono::{NaiveDate, Utc};
+    use std::fs;
+
+    fn date() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2023, 7, 1).unwrap().and_hms_opt(14, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn reads_offset_from_exif_fields() {
+        let mut exif = ExifData::default();
+        assert_eq!(exif.date_taken_offset(), None);
+        exif.other_fields.insert("OffsetTimeOriginal (IFD0)".to_string(), "-05:00".to_string());
+        assert_eq!(exif.date_taken_offset(), FixedOffset::west_opt(5 * 3600));
+    }
+
+    #[test]
+    fn uses_exif_offset_when_present_and_local_time_otherwise() {
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        let expected_utc = NaiveDate::from_ymd_opt(2023, 7, 1).unwrap().and_hms_opt(5, 30, 0).unwrap();
+        assert_eq!(capture_time(date(), Some(tokyo)), Some(SystemTime::from(Utc.from_utc_datetime(&expected_utc))));
+        assert_eq!(capture_time(date(), None), Local.from_local_datetime(&date()).earliest().map(SystemTime::from));
+    }
+
+    #[test]
+    fn sets_file_times_and_undo_restores_them() {
+        let dir = test_dir("timestamps_undo");
+        let path = dir.join("a.jpg");
+        fs::write(&path, "a").unwrap();
+        let previous = fs::metadata(&path).unwrap().modified().unwrap();
+        let offset = FixedOffset::east_opt(2 * 3600);
+        let journal = Journal::new(dir.join("journal.jsonl"));
+        let report = set_file_times_from_dates(&[(path.clone(), date(), offset)], &journal);
+        assert_eq!((report.updated, report.errors.len()), (1, 0));
+        assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), capture_time(date(), offset).unwrap());
+        // Déjà à la bonne date : ignoré
+        assert_eq!(set_file_times_from_dates(&[(path.clone(), date(), offset)], &journal).skipped, 1);
+
+        for entry in journal.read_all().unwrap() { undo_operation(&entry.operation).unwrap(); }
+        assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), previous);
+    }
+}
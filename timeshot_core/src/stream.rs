@@ -0,0 +1,279 @@
+// timeshot_core/src/stream.rs
+//
+// Analyse en flux : les fichiers sont envoyés un par un depuis un thread de travail,
+// puis une passe finale résout les hashes et les doublons, qui dépendent de l'ensemble des fichiers.
+
+use crate::cache::AnalysisCache;
+use crate::exif::reader::read_exif_data;
+use crate::hash::compute::compute_file_hash_with;
+use crate::hash::detect::{fill_full_hash, hash_duplicate_candidates, mark_duplicates, mark_probable_duplicates};
+use crate::hash::perceptual::compute_perceptual_hash;
+use crate::renamer::generator::generate_filename;
+use crate::renamer::template::{NameCounters, NameTemplate};
+use crate::types::{AnalysisOptions, CacheMode, ExifData, FileAnalysis};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use walkdir::{DirEntry, WalkDir};
+
+/// Événements émis pendant une analyse en flux.
+#[derive(Debug, Clone)]
+pub enum AnalysisEvent {
+    /// Fichier analysé. Son indice est l'ordre d'émission (0, 1, 2...).
+    /// Le nom proposé est définitif (compteurs de rafale tenus dans l'ordre de parcours) ;
+    /// le hash et le statut de doublon sont complétés par `Finished`.
+    File(Box<FileAnalysis>),
+    /// Chemin ignoré ou entrée illisible (l'analyse continue).
+    Error(String),
+    /// Passe finale : mises à jour des fichiers dont le hash ou le statut de doublon a changé.
+    Finished(Vec<DuplicateUpdate>),
+    /// Après `Finished` : hashes complets des fichiers qui n'en avaient pas besoin pour la détection
+    /// des doublons (taille unique...), envoyés par paquets. Le flux se termine après le dernier.
+    Hashes(Vec<DuplicateUpdate>),
+}
+
+/// Nombre de hashes complétés par événement `Hashes`.
+const HASH_UPDATES_PER_EVENT: usize = 100;
+
+/// Résultat de la passe finale pour le fichier émis en position `index`.
+#[derive(Debug, Clone)]
+pub struct DuplicateUpdate {
+    pub index: usize,
+    pub file_hash: Option<String>,
+    pub hash_error: Option<String>,
+    pub is_duplicate: bool,
+    pub is_probable_duplicate: bool,
+}
+
+impl DuplicateUpdate {
+    fn from_record(index: usize, record: &FileAnalysis) -> Self {
+        DuplicateUpdate {
+            index,
+            file_hash: record.file_hash.clone(),
+            hash_error: record.hash_error.clone(),
+            is_duplicate: record.is_duplicate,
+            is_probable_duplicate: record.is_probable_duplicate,
+        }
+    }
+
+    pub fn apply(&self, analysis: &mut FileAnalysis) {
+        analysis.file_hash = self.file_hash.clone();
+        analysis.hash_error = self.hash_error.clone();
+        analysis.is_duplicate = self.is_duplicate;
+        analysis.is_probable_duplicate = self.is_probable_duplicate;
+    }
+}
+
+/// Flux d'événements d'analyse ; se termine après `Finished` et les éventuels `Hashes`.
+pub struct AnalysisStream {
+    receiver: Receiver<AnalysisEvent>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl AnalysisStream {
+    /// Récepteur brut, pour une lecture non bloquante (`try_recv`) depuis une boucle d'interface.
+    pub fn receiver(&self) -> &Receiver<AnalysisEvent> {
+        &self.receiver
+    }
+}
+
+impl Iterator for AnalysisStream {
+    type Item = AnalysisEvent;
+
+    fn next(&mut self) -> Option<AnalysisEvent> {
+        match self.receiver.recv() {
+            Ok(event) => Some(event),
+            Err(_) => {
+                if let Some(worker) = self.worker.take() {
+                    let _ = worker.join();
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Lance l'analyse dans un thread et renvoie immédiatement le flux d'événements.
+pub fn analyze_streaming(dir_paths: Vec<PathBuf>, options: AnalysisOptions) -> Result<AnalysisStream, String> {
+    if !dir_paths.is_empty() && !dir_paths.iter().any(|p| p.is_dir()) {
+        return Err("Aucun chemin de dossier valide fourni.".to_string());
+    }
+    let (sender, receiver) = mpsc::channel();
+    let worker = thread::Builder::new()
+        .name("timeshot-analyse".to_string())
+        .spawn(move || run_analysis(dir_paths, &options, &sender))
+        .map_err(|e| format!("Impossible de lancer l'analyse : {}", e))?;
+    Ok(AnalysisStream { receiver, worker: Some(worker) })
+}
+
+fn run_analysis(dir_paths: Vec<PathBuf>, options: &AnalysisOptions, sender: &Sender<AnalysisEvent>) {
+    let scan_type = if options.recursive { "récursive" } else { "simple" };
+    println!("🔍 Lancement analyse {} sur {} répertoire(s)...", scan_type, dir_paths.len());
+
+    let mut cache = open_cache(options);
+    let template = match options.name_template.as_deref().map(NameTemplate::parse) {
+        Some(Ok(template)) => template,
+        Some(Err(e)) => {
+            eprintln!("Attention : modèle de nommage invalide ({}), modèle par défaut utilisé", e);
+            let _ = sender.send(AnalysisEvent::Error(format!("Modèle de nommage invalide : {}", e)));
+            NameTemplate::default()
+        }
+        None => NameTemplate::default(),
+    };
+    let mut name_counter = NameCounters::default();
+    // Seul un résumé de chaque fichier (sans les EXIF) est gardé pour la passe finale
+    let mut records: Vec<FileAnalysis> = Vec::new();
+    let mut hash_known: Vec<bool> = Vec::new();
+    let mut error_count = 0;
+    let mut cache_hits = 0;
+
+    for dir_path in &dir_paths {
+        if !dir_path.is_dir() {
+            let error_msg = format!("Chemin fourni n'est pas un répertoire valide et sera ignoré : {}", dir_path.display());
+            eprintln!("Attention : {}", error_msg); error_count += 1;
+            let _ = sender.send(AnalysisEvent::Error(error_msg));
+            continue;
+        }
+        println!("  -> Analyse de : {}", dir_path.display());
+        let mut walker_builder = WalkDir::new(dir_path).min_depth(1);
+        if !options.recursive { walker_builder = walker_builder.max_depth(1); }
+        for entry_result in walker_builder.into_iter() {
+            match entry_result {
+                Ok(entry) => { if entry.file_type().is_file() {
+                    let (analysis, from_cache) = analyze_entry(&entry, dir_path, options, cache.as_mut(), &template, &mut name_counter);
+                    if from_cache { cache_hits += 1; }
+                    hash_known.push(analysis.file_hash.is_some());
+                    records.push(FileAnalysis { exif: ExifData::default(), ..analysis.clone() });
+                    // Le récepteur a pu être abandonné : on termine quand même pour mettre à jour le cache
+                    let _ = sender.send(AnalysisEvent::File(Box::new(analysis)));
+                } }
+                Err(e) => {
+                    let error_msg = format!("Erreur lecture entrée dans {}: {}", dir_path.display(), e);
+                    eprintln!("Attention : {}", error_msg); error_count += 1;
+                    let _ = sender.send(AnalysisEvent::Error(error_msg));
+                }
+            }
+        }
+    }
+
+    // --- Passe finale : hashes, doublons exacts et probables ---
+    if !options.hash_all_files {
+        println!("🔍 Hachage des fichiers candidats aux doublons (même taille)...");
+        hash_duplicate_candidates(&mut records);
+    }
+    println!("🔍 Marquage des doublons sur l'ensemble des {} fichiers trouvés...", records.len());
+    mark_duplicates(&mut records);
+    if let Some(threshold) = options.similarity_threshold {
+        println!("🔍 Recherche des doublons probables (seuil de similarité : {})...", threshold);
+        mark_probable_duplicates(&mut records, threshold);
+    }
+
+    let mut updates = Vec::new();
+    for (index, (record, hash_was_known)) in records.iter().zip(&hash_known).enumerate() {
+        let hash_computed = !hash_was_known && record.file_hash.is_some();
+        if hash_computed {
+            if let (Some(cache), Some(hash)) = (cache.as_mut(), &record.file_hash) {
+                cache.set_file_hash(&record.full_original_path, hash, record.hash_algorithm);
+            }
+        }
+        if hash_computed || record.hash_error.is_some() || record.is_duplicate || record.is_probable_duplicate {
+            updates.push(DuplicateUpdate::from_record(index, record));
+        }
+    }
+    let _ = sender.send(AnalysisEvent::Finished(updates));
+
+    // Les doublons sont connus : les hashes restants sont complétés ensuite (colonne, exports)
+    let remaining: Vec<usize> = (0..records.len()).filter(|&i| records[i].file_hash.is_none() && records[i].hash_error.is_none()).collect();
+    if !remaining.is_empty() {
+        println!("🔍 Hachage des {} fichier(s) restant(s)...", remaining.len());
+    }
+    for chunk in remaining.chunks(HASH_UPDATES_PER_EVENT) {
+        let mut updates = Vec::with_capacity(chunk.len());
+        for &index in chunk {
+            let record = &mut records[index];
+            fill_full_hash(record);
+            if let (Some(cache), Some(hash)) = (cache.as_mut(), &record.file_hash) {
+                cache.set_file_hash(&record.full_original_path, hash, record.hash_algorithm);
+            }
+            updates.push(DuplicateUpdate::from_record(index, record));
+        }
+        // Récepteur abandonné (nouvelle analyse) : inutile de continuer
+        if sender.send(AnalysisEvent::Hashes(updates)).is_err() { break; }
+    }
+
+    if let Some(cache) = &mut cache {
+        // Fichiers supprimés, renommés ou déplacés depuis leur mise en cache
+        cache.prune_missing();
+        if let Err(e) = cache.save() {
+            eprintln!("Attention : écriture du cache impossible ({}): {}", cache.path().display(), e);
+        }
+        println!("💾 Cache : {} fichier(s) repris sans relecture.", cache_hits);
+    }
+    println!("✅ Analyse {} terminée. {} fichiers traités au total.", scan_type, records.len());
+    if error_count > 0 { println!("⚠️ {} erreurs rencontrées pendant l'analyse.", error_count); }
+}
+
+/// Analyse un fichier (EXIF, hash éventuel, hash perceptuel, nom proposé), en passant par le cache.
+/// Renvoie aussi si les données viennent du cache.
+fn analyze_entry(
+    entry: &DirEntry,
+    dir_path: &Path,
+    options: &AnalysisOptions,
+    cache: Option<&mut AnalysisCache>,
+    template: &NameTemplate,
+    name_counter: &mut NameCounters,
+) -> (FileAnalysis, bool) {
+    let file_path = entry.path();
+    let original_name = entry.file_name().to_string_lossy().to_string();
+    let parent_path = file_path.parent().unwrap_or(dir_path);
+    let current_folder_name = parent_path.file_name().map(|name| name.to_string_lossy().replace(' ', "_")).unwrap_or_else(|| "racine".to_string());
+    let metadata = entry.metadata().ok();
+    let file_size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+    let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+
+    let cached = match (&cache, modified) {
+        (Some(cache), Some(modified)) => cache.lookup(file_path, file_size, modified).cloned(),
+        _ => None,
+    };
+    let exif_data = match &cached { Some(c) => c.exif.clone(), None => read_exif_data(file_path) };
+    // Un hash calculé avec un autre algorithme n'est pas réutilisable
+    let cached_hash = cached.as_ref().filter(|c| c.hash_algorithm == options.hash_algorithm).and_then(|c| c.file_hash.clone());
+    let (file_hash, hash_error) = match cached_hash {
+        Some(hash) => (Some(hash), None),
+        None if options.hash_all_files => match compute_file_hash_with(file_path, options.hash_algorithm) {
+            Ok(hash) => (Some(hash), None),
+            Err(e) => {
+                eprintln!("Attention : hash impossible pour '{}': {}", file_path.display(), e);
+                (None, Some(e.to_string()))
+            }
+        },
+        None => (None, None),
+    };
+    let perceptual_computed = options.similarity_threshold.is_some() || cached.as_ref().is_some_and(|c| c.perceptual_computed);
+    let perceptual_hash = match (&cached, options.similarity_threshold) {
+        (Some(c), Some(_)) if c.perceptual_computed => c.perceptual_hash,
+        (_, Some(_)) => compute_perceptual_hash(file_path),
+        (Some(c), None) => c.perceptual_hash,
+        (None, None) => None,
+    };
+
+    let mut analysis = FileAnalysis { full_original_path: file_path.to_path_buf(), original_name, folder_name: current_folder_name, file_size, exif: exif_data, new_name: None, file_hash, hash_algorithm: options.hash_algorithm, hash_error, is_duplicate: false, perceptual_hash, is_probable_duplicate: false };
+    analysis.new_name = Some(generate_filename(&analysis, template, name_counter));
+
+    // Sans date de modification, impossible de valider l'entrée plus tard
+    if let (Some(cache), Some(modified)) = (cache, modified) {
+        cache.update(&analysis, modified, perceptual_computed);
+    }
+    (analysis, cached.is_some())
+}
+
+fn open_cache(options: &AnalysisOptions) -> Option<AnalysisCache> {
+    if options.cache == CacheMode::Disabled { return None; }
+    let path = match options.cache_path.clone().map(Ok).unwrap_or_else(AnalysisCache::default_path) {
+        Ok(path) => path,
+        Err(e) => { eprintln!("Attention : cache désactivé ({})", e); return None; }
+    };
+    let mut cache = AnalysisCache::open(path);
+    if options.cache == CacheMode::Rebuild { cache.clear(); }
+    Some(cache)
+}
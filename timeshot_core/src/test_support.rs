@@ -0,0 +1,52 @@
+// timeshot_core/src/test_support.rs
+//
+// Outils communs aux tests : dossiers temporaires et analyses minimales.
+
+use crate::types::{ExifData, FileAnalysis};
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// Dossier temporaire vide, supprimé (avec son contenu) à la fin du test.
+pub struct TestDir(PathBuf);
+
+impl Deref for TestDir {
+    type Target = Path;
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// `name` doit être unique parmi les tests (ex: "writer_fits").
+pub fn test_dir(name: &str) -> TestDir {
+    let dir = std::env::temp_dir().join(format!("timeshot_{}_{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    TestDir(dir)
+}
+
+/// Analyse sans métadonnées du fichier `path` (nom et dossier déduits du chemin).
+pub fn file_analysis(path: impl AsRef<Path>) -> FileAnalysis {
+    let path = path.as_ref();
+    let name = |p: Option<&Path>| p.and_then(Path::file_name).map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    FileAnalysis {
+        full_original_path: path.to_path_buf(),
+        original_name: name(Some(path)),
+        folder_name: name(path.parent()),
+        file_size: 0,
+        exif: ExifData::default(),
+        new_name: None,
+        file_hash: None,
+        hash_algorithm: Default::default(),
+        hash_error: None,
+        is_duplicate: false,
+        perceptual_hash: None,
+        is_probable_duplicate: false,
+    }
+}
@@ -1,12 +1,14 @@
 // timeshot_core/src/types.rs
 
-use chrono::NaiveDateTime;
+use chrono::{FixedOffset, NaiveDateTime};
 use std::collections::HashMap;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf; // Gardé car utilisé dans FileAnalysis
+use crate::filename::parser::{detect_date_pattern_in_filename, DateDetection};
+use crate::hash::compute::HashAlgorithm;
 
 // Correction de l'attribut derive
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ExifData {
     pub date_taken: Option<NaiveDateTime>,
     pub create_date: Option<NaiveDateTime>,
@@ -21,14 +23,128 @@ pub struct ExifData {
 }
 
 // Correction de l'attribut derive
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileAnalysis {
     #[serde(skip)]
     pub full_original_path: PathBuf,
     pub original_name: String,
     pub folder_name: String,
+    /// Taille du fichier en octets.
+    pub file_size: u64,
     pub exif: ExifData,
     pub new_name: Option<String>,
     pub file_hash: Option<String>,
+    /// Algorithme utilisé pour `file_hash`.
+    pub hash_algorithm: HashAlgorithm,
+    /// Erreur de lecture pendant le calcul du hash : le fichier est exclu de la détection de doublons.
+    pub hash_error: Option<String>,
     pub is_duplicate: bool,
-}
\ No newline at end of file
+    /// dHash 64 bits (images uniquement, si demandé dans les options).
+    pub perceptual_hash: Option<u64>,
+    /// Image visuellement proche d'une autre (redimensionnée, ré-encodée...).
+    pub is_probable_duplicate: bool,
+}
+
+impl ExifData {
+    /// Décalage horaire de la prise de vue (EXIF `OffsetTimeOriginal`, ex: "+02:00"), s'il est renseigné.
+    pub fn date_taken_offset(&self) -> Option<FixedOffset> {
+        self.other_fields
+            .iter()
+            .find(|(key, _)| key.split(" (").next() == Some("OffsetTimeOriginal"))
+            .and_then(|(_, value)| value.trim().parse().ok())
+    }
+}
+
+impl FileAnalysis {
+    /// Meilleure date disponible et son origine : date de prise de vue, puis dates EXIF
+    /// de création et de modification, puis date complète trouvée dans le nom du fichier.
+    pub fn best_date(&self) -> (Option<NaiveDateTime>, DateSource) {
+        if let Some(date) = self.exif.date_taken { return (Some(date), DateSource::DateTimeOriginal); }
+        if let Some(date) = self.exif.create_date { return (Some(date), DateSource::CreateDate); }
+        if let Some(date) = self.exif.modify_date { return (Some(date), DateSource::ModifyDate); }
+        match detect_date_pattern_in_filename(&self.original_name) {
+            DateDetection::FoundValidFormat(date) => (Some(date), DateSource::FileName),
+            _ => (None, DateSource::None),
+        }
+    }
+}
+
+/// Origine d'une date (voir `FileAnalysis::best_date`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateSource {
+    /// EXIF `DateTimeOriginal`.
+    DateTimeOriginal,
+    /// EXIF `CreateDate`.
+    CreateDate,
+    /// EXIF `ModifyDate`.
+    ModifyDate,
+    /// Date au format `YYYY-MM-DD_HHMMSS` dans le nom du fichier.
+    FileName,
+    None,
+}
+
+impl DateSource {
+    /// Identifiant court, utilisé dans les exports.
+    pub fn name(self) -> &'static str {
+        match self {
+            DateSource::DateTimeOriginal => "date_time_original",
+            DateSource::CreateDate => "create_date",
+            DateSource::ModifyDate => "modify_date",
+            DateSource::FileName => "file_name",
+            DateSource::None => "none",
+        }
+    }
+
+    /// Libellé affiché.
+    pub fn label(self) -> &'static str {
+        match self {
+            DateSource::DateTimeOriginal => "EXIF (prise de vue)",
+            DateSource::CreateDate => "EXIF (numérisation)",
+            DateSource::ModifyDate => "EXIF (modification)",
+            DateSource::FileName => "Nom du fichier",
+            DateSource::None => "Aucune date",
+        }
+    }
+}
+
+/// Options d'analyse des répertoires.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnalysisOptions {
+    pub recursive: bool,
+    /// Distance de Hamming max entre hashes perceptuels pour signaler un doublon probable.
+    /// `None` : pas de hash perceptuel (évite de décoder toutes les images).
+    pub similarity_threshold: Option<u32>,
+    /// Calcule le hash de tous les fichiers pendant le parcours. Sinon, seuls les fichiers susceptibles
+    /// d'avoir un doublon (même taille, même début et même fin) sont hachés avant la détection des
+    /// doublons ; les autres le sont ensuite (`AnalysisEvent::Hashes`). Tous les fichiers ont leur hash à la fin.
+    pub hash_all_files: bool,
+    /// Algorithme des hashes complets (doublons exacts, manifestes).
+    pub hash_algorithm: HashAlgorithm,
+    pub cache: CacheMode,
+    /// Fichier de cache à utiliser (ex: à la racine de la bibliothèque). Par défaut : dossier de cache utilisateur.
+    pub cache_path: Option<PathBuf>,
+    /// Modèle de nommage (`renamer::template`). Par défaut : `DEFAULT_TEMPLATE`.
+    pub name_template: Option<String>,
+}
+
+/// Utilisation du cache persistant d'analyse.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CacheMode {
+    #[default]
+    Disabled,
+    /// Réutilise les entrées valides et enregistre les nouvelles.
+    Enabled,
+    /// Ignore les entrées existantes et reconstruit le cache à partir de cette analyse.
+    Rebuild,
+}
+
+/// Taille lisible (ex: "12,3 Mo").
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["o", "Ko", "Mo", "Go"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 { size /= 1024.0; unit += 1; }
+    if unit == 0 { format!("{} {}", bytes, UNITS[0]) } else { format!("{:.1} {}", size, UNITS[unit]).replace('.', ",") }
+}
@@ -0,0 +1,185 @@
+// timeshot_gui/src/columns.rs
+
+use crate::file_data_item::FileDataItem;
+use gtk4::glib::{self, KeyFile, KeyFileFlags};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Box as GtkBox, CheckButton, ColumnViewColumn, CustomSorter, Expression, Label, ListItem,
+    MenuButton, Orientation, Popover, PropertyExpression, SignalListItemFactory, StringSorter,
+};
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+use timeshot_core::types::{format_size, DateSource, FileAnalysis};
+
+const SETTINGS_GROUP: &str = "Colonnes";
+const SETTINGS_KEY: &str = "visibles";
+
+/// Colonnes facultatives de la liste des résultats (choix mémorisé entre les sessions).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtraColumn {
+    Folder,
+    Camera,
+    Lens,
+    FileSize,
+    Extension,
+    DateSource,
+    FullPath,
+}
+
+impl ExtraColumn {
+    pub const ALL: [ExtraColumn; 7] = [
+        ExtraColumn::Folder, ExtraColumn::Camera, ExtraColumn::Lens, ExtraColumn::FileSize,
+        ExtraColumn::Extension, ExtraColumn::DateSource, ExtraColumn::FullPath,
+    ];
+
+    /// Identifiant enregistré dans le fichier de préférences.
+    fn key(self) -> &'static str {
+        match self {
+            ExtraColumn::Folder => "folder",
+            ExtraColumn::Camera => "camera",
+            ExtraColumn::Lens => "lens",
+            ExtraColumn::FileSize => "file_size",
+            ExtraColumn::Extension => "extension",
+            ExtraColumn::DateSource => "date_source",
+            ExtraColumn::FullPath => "full_path",
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            ExtraColumn::Folder => "Dossier",
+            ExtraColumn::Camera => "Appareil",
+            ExtraColumn::Lens => "Objectif",
+            ExtraColumn::FileSize => "Taille",
+            ExtraColumn::Extension => "Extension",
+            ExtraColumn::DateSource => "Origine de la date",
+            ExtraColumn::FullPath => "Chemin complet",
+        }
+    }
+
+    fn text(self, analysis: &FileAnalysis) -> String {
+        match self {
+            ExtraColumn::Folder => analysis.folder_name.clone(),
+            ExtraColumn::Camera => analysis.exif.camera_model.clone().unwrap_or_default(),
+            ExtraColumn::Lens => analysis.exif.lens_model.clone().unwrap_or_default(),
+            ExtraColumn::FileSize => format_size(analysis.file_size),
+            ExtraColumn::Extension => extension(analysis),
+            ExtraColumn::DateSource => match analysis.best_date().1 { DateSource::None => String::new(), source => source.label().to_string() },
+            ExtraColumn::FullPath => analysis.full_original_path.to_string_lossy().into_owned(),
+        }
+    }
+
+    fn compare(self, a: &FileAnalysis, b: &FileAnalysis) -> Ordering {
+        match self {
+            ExtraColumn::FileSize => a.file_size.cmp(&b.file_size),
+            ExtraColumn::FullPath => a.full_original_path.cmp(&b.full_original_path),
+            _ => self.text(a).to_lowercase().cmp(&self.text(b).to_lowercase()),
+        }
+    }
+
+    fn is_wide(self) -> bool {
+        matches!(self, ExtraColumn::FullPath | ExtraColumn::Lens | ExtraColumn::Camera)
+    }
+}
+
+fn extension(analysis: &FileAnalysis) -> String {
+    Path::new(&analysis.original_name).extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default()
+}
+
+fn file_item(object: &glib::Object) -> Option<&FileDataItem> {
+    object.downcast_ref::<FileDataItem>()
+}
+
+/// Tri alphabétique (sans tenir compte de la casse) sur une propriété texte de `FileDataItem`.
+pub fn property_sorter(property_name: &str) -> StringSorter {
+    StringSorter::new(Some(PropertyExpression::new(FileDataItem::static_type(), None::<&Expression>, property_name)))
+}
+
+/// Tri de la colonne "Statut" : erreurs de lecture, doublons, doublons probables, puis le reste.
+pub fn status_sorter() -> CustomSorter {
+    let rank = |item: &FileDataItem| {
+        if !item.property::<String>("hash-error").is_empty() { 0 }
+        else if item.property::<bool>("is-duplicate") { 1 }
+        else if item.property::<bool>("is-probable-duplicate") { 2 }
+        else { 3 }
+    };
+    CustomSorter::new(move |a, b| match (file_item(a), file_item(b)) {
+        (Some(a), Some(b)) => rank(a).cmp(&rank(b)).into(),
+        _ => gtk4::Ordering::Equal,
+    })
+}
+
+/// Colonne facultative triable (masquée tant qu'elle n'est pas choisie).
+pub fn create_extra_column(column: ExtraColumn) -> ColumnViewColumn {
+    let factory = SignalListItemFactory::new();
+    factory.connect_setup(move |_factory, list_item| {
+        let label = Label::builder().halign(Align::Start).hexpand(true).ellipsize(gtk4::pango::EllipsizeMode::Middle).build();
+        list_item.downcast_ref::<ListItem>().expect("Needs ListItem").set_child(Some(&label));
+    });
+    factory.connect_bind(move |_factory, list_item| {
+        let list_item = list_item.downcast_ref::<ListItem>().expect("Needs ListItem");
+        let Some(item) = list_item.item().and_then(|o| o.downcast::<FileDataItem>().ok()) else { return };
+        let Some(label) = list_item.child().and_then(|c| c.downcast::<Label>().ok()) else { return };
+        let text = item.with_analysis(|analysis| column.text(analysis)).unwrap_or_default();
+        label.set_tooltip_text(if column == ExtraColumn::FullPath { Some(&text) } else { None });
+        label.set_text(if text.is_empty() { "-" } else { &text });
+    });
+    let sorter = CustomSorter::new(move |a, b| {
+        let ordering = file_item(a).zip(file_item(b)).and_then(|(a, b)| {
+            a.with_analysis(|a| b.with_analysis(|b| column.compare(a, b))).flatten()
+        });
+        ordering.unwrap_or(Ordering::Equal).into()
+    });
+    let builder = ColumnViewColumn::builder().title(column.title()).factory(&factory).sorter(&sorter).resizable(true).visible(false);
+    if column.is_wide() { builder.expand(true).build() } else { builder.fixed_width(130).build() }
+}
+
+fn settings_path() -> PathBuf {
+    glib::user_config_dir().join("timeshot").join("gui.ini")
+}
+
+/// Colonnes facultatives affichées lors de la dernière session (aucune par défaut).
+fn load_visible_columns() -> Vec<ExtraColumn> {
+    let key_file = KeyFile::new();
+    if key_file.load_from_file(settings_path(), KeyFileFlags::NONE).is_err() { return Vec::new(); }
+    let Ok(keys) = key_file.string(SETTINGS_GROUP, SETTINGS_KEY) else { return Vec::new() };
+    ExtraColumn::ALL.into_iter().filter(|column| keys.split(';').any(|k| k.trim() == column.key())).collect()
+}
+
+fn save_visible_columns(columns: &[ExtraColumn]) {
+    let path = settings_path();
+    let key_file = KeyFile::new();
+    // Conserve les autres réglages éventuels du fichier
+    let _ = key_file.load_from_file(&path, KeyFileFlags::KEEP_COMMENTS);
+    let keys: Vec<&str> = columns.iter().map(|c| c.key()).collect();
+    key_file.set_string(SETTINGS_GROUP, SETTINGS_KEY, &keys.join(";"));
+    let result = match path.parent() {
+        Some(parent) => std::fs::create_dir_all(parent).map_err(|e| e.to_string()),
+        None => Ok(()),
+    }
+    .and_then(|_| key_file.save_to_file(&path).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        eprintln!("Attention : préférences de colonnes non enregistrées ({}) : {}", path.display(), e);
+    }
+}
+
+/// Bouton "Colonnes" : une case par colonne facultative, choix enregistré à chaque changement.
+pub fn create_column_chooser(columns: &[(ExtraColumn, ColumnViewColumn)]) -> MenuButton {
+    let visible = load_visible_columns();
+    let list_box = GtkBox::new(Orientation::Vertical, 4);
+    list_box.set_margin_top(6); list_box.set_margin_bottom(6); list_box.set_margin_start(6); list_box.set_margin_end(6);
+    for (column, view_column) in columns {
+        view_column.set_visible(visible.contains(column));
+        let check = CheckButton::builder().label(column.title()).active(visible.contains(column)).build();
+        let view_column = view_column.clone();
+        let all_columns = columns.to_vec();
+        check.connect_toggled(move |check| {
+            view_column.set_visible(check.is_active());
+            let shown: Vec<ExtraColumn> = all_columns.iter().filter(|(_, c)| c.is_visible()).map(|(column, _)| *column).collect();
+            save_visible_columns(&shown);
+        });
+        list_box.append(&check);
+    }
+    let popover = Popover::builder().child(&list_box).build();
+    MenuButton::builder().label("Colonnes").tooltip_text("Colonnes supplémentaires à afficher").popover(&popover).build()
+}
@@ -0,0 +1,322 @@
+// timeshot_gui/src/duplicates_view.rs
+
+use crate::file_data_item::FileDataItem;
+use crate::history_dialog::RemovedItems;
+use crate::thumbnails::{ThumbnailLoader, LIST_THUMBNAIL_SIZE};
+use gtk4::gdk::Texture;
+use gtk4::gio::{self, ListStore};
+use gtk4::glib::{self, clone};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, AlertDialog, Box as GtkBox, Button, CheckButton, ContentFit, DropDown, Entry, Label, ListBox,
+    Orientation, Picture, PolicyType, ScrolledWindow, SelectionMode, StringObject, Window,
+};
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use timeshot_core::hash::groups::{find_duplicate_groups, DuplicateGroup, KeeperStrategy};
+use timeshot_core::hash::resolve::{resolve_duplicates, ResolutionAction};
+use timeshot_core::journal::store::Journal;
+use timeshot_core::journal::trash::{empty_trash, trash_usage};
+use timeshot_core::types::{format_size, FileAnalysis};
+
+const ACTION_LABELS: [&str; 3] = ["Quarantaine", "Lien physique", "Corbeille"];
+const STRATEGY_LABELS: [&str; 4] = ["Plus ancien", "Chemin le plus court", "Meilleures métadonnées", "Dossier préféré"];
+/// Position de "Dossier préféré" dans `STRATEGY_LABELS`.
+const PREFERRED_FOLDER_STRATEGY: u32 = 3;
+
+/// Un groupe affiché : case de sélection du groupe et bouton radio de chaque membre (fichier conservé).
+struct GroupRow {
+    group: DuplicateGroup,
+    check: CheckButton,
+    keeper_radios: Vec<(usize, CheckButton)>,
+}
+
+impl GroupRow {
+    /// Groupe avec le fichier conservé choisi dans la liste.
+    fn group_with_keeper(&self) -> DuplicateGroup {
+        let keeper = self.keeper_radios.iter().find(|(_, radio)| radio.is_active()).map(|(index, _)| *index).unwrap_or(self.group.keeper);
+        DuplicateGroup { keeper, ..self.group.clone() }
+    }
+}
+
+#[derive(Default)]
+struct ViewState {
+    files: Vec<FileAnalysis>,
+    rows: Vec<GroupRow>,
+}
+
+/// Onglet "Doublons" : groupes de doublons exacts des résultats, avec vignettes, choix du fichier
+/// conservé et action appliquée aux groupes cochés.
+#[derive(Clone)]
+pub struct DuplicatesView {
+    root: GtkBox,
+    summary_label: Label,
+    strategy_dropdown: DropDown,
+    preferred_folder_entry: Entry,
+    quarantine_entry: Entry,
+    action_dropdown: DropDown,
+    groups_list: ListBox,
+    results_model: ListStore,
+    directory_store: ListStore,
+    thumbnail_loader: ThumbnailLoader,
+    removed_items: RemovedItems,
+    state: Rc<RefCell<ViewState>>,
+}
+
+impl DuplicatesView {
+    pub fn new(results_model: &ListStore, directory_store: &ListStore, thumbnail_loader: &ThumbnailLoader, removed_items: &RemovedItems) -> Self {
+        let root = GtkBox::new(Orientation::Vertical, 6);
+        root.set_margin_top(6);
+        let summary_label = Label::builder().halign(Align::Start).css_classes(vec!["heading".to_string()]).build();
+
+        let options_hbox = GtkBox::new(Orientation::Horizontal, 6);
+        let strategy_dropdown = DropDown::from_strings(&STRATEGY_LABELS);
+        let preferred_folder_entry = Entry::builder().hexpand(true).visible(false).placeholder_text("Dossier dont les fichiers sont conservés (sinon : plus ancien)").build();
+        let quarantine_entry = Entry::builder().hexpand(true).placeholder_text("Dossier de quarantaine").build();
+        let refresh_button = Button::with_label("Actualiser");
+        options_hbox.append(&Label::new(Some("Fichier conservé par défaut :"))); options_hbox.append(&strategy_dropdown); options_hbox.append(&preferred_folder_entry);
+        options_hbox.append(&Label::new(Some("Quarantaine :"))); options_hbox.append(&quarantine_entry); options_hbox.append(&refresh_button);
+
+        let groups_list = ListBox::new();
+        groups_list.set_selection_mode(SelectionMode::None);
+        let scrolled = ScrolledWindow::builder().hscrollbar_policy(PolicyType::Automatic).vscrollbar_policy(PolicyType::Automatic).child(&groups_list).vexpand(true).build();
+
+        let actions_hbox = GtkBox::new(Orientation::Horizontal, 6);
+        let check_all_button = Button::with_label("Tout cocher");
+        let uncheck_all_button = Button::with_label("Tout décocher");
+        let action_dropdown = DropDown::from_strings(&ACTION_LABELS);
+        let apply_button = Button::with_label("Appliquer aux groupes cochés"); apply_button.add_css_class("destructive-action");
+        let empty_trash_button = Button::builder().label("Vider la corbeille...").tooltip_text("Supprime définitivement les doublons mis à la corbeille et les sauvegardes des liens physiques (plus annulables)").build();
+        actions_hbox.append(&check_all_button); actions_hbox.append(&uncheck_all_button); actions_hbox.append(&empty_trash_button);
+        actions_hbox.append(&GtkBox::builder().orientation(Orientation::Horizontal).hexpand(true).build()); // Spacer
+        actions_hbox.append(&action_dropdown); actions_hbox.append(&apply_button);
+        root.append(&summary_label); root.append(&options_hbox); root.append(&scrolled); root.append(&actions_hbox);
+
+        let view = DuplicatesView {
+            root, summary_label, strategy_dropdown, preferred_folder_entry, quarantine_entry, action_dropdown, groups_list,
+            results_model: results_model.clone(), directory_store: directory_store.clone(),
+            thumbnail_loader: thumbnail_loader.clone(), removed_items: removed_items.clone(), state: Rc::new(RefCell::new(ViewState::default())),
+        };
+        let view_refresh = view.clone();
+        refresh_button.connect_clicked(move |_| view_refresh.refresh());
+        let view_strategy = view.clone();
+        view.strategy_dropdown.connect_selected_notify(move |dropdown| {
+            view_strategy.preferred_folder_entry.set_visible(dropdown.selected() == PREFERRED_FOLDER_STRATEGY);
+            view_strategy.refresh();
+        });
+        let view_folder = view.clone();
+        view.preferred_folder_entry.connect_activate(move |_| view_folder.refresh());
+        let view_check = view.clone();
+        check_all_button.connect_clicked(move |_| view_check.set_all_checked(true));
+        let view_uncheck = view.clone();
+        uncheck_all_button.connect_clicked(move |_| view_uncheck.set_all_checked(false));
+        let view_apply = view.clone();
+        apply_button.connect_clicked(move |_| view_apply.apply());
+        let view_trash = view.clone();
+        empty_trash_button.connect_clicked(move |_| view_trash.empty_trash());
+        view
+    }
+
+    pub fn widget(&self) -> &GtkBox {
+        &self.root
+    }
+
+    fn base_dirs(&self) -> Vec<PathBuf> {
+        (0..self.directory_store.n_items())
+            .filter_map(|i| self.directory_store.item(i).and_then(|o| o.downcast::<StringObject>().ok()))
+            .map(|s| PathBuf::from(s.string()))
+            .collect()
+    }
+
+    fn strategy(&self) -> KeeperStrategy {
+        match self.strategy_dropdown.selected() {
+            1 => KeeperStrategy::ShortestPath,
+            2 => KeeperStrategy::BestMetadata,
+            PREFERRED_FOLDER_STRATEGY if !self.preferred_folder_entry.text().trim().is_empty() => {
+                KeeperStrategy::PreferredFolder(PathBuf::from(self.preferred_folder_entry.text().trim()))
+            }
+            _ => KeeperStrategy::Oldest,
+        }
+    }
+
+    /// Recalcule les groupes à partir des résultats actuels.
+    pub fn refresh(&self) {
+        let files: Vec<FileAnalysis> = (0..self.results_model.n_items())
+            .filter_map(|i| self.results_model.item(i).and_then(|o| o.downcast::<FileDataItem>().ok()))
+            .filter_map(|item| item.analysis())
+            .collect();
+        let groups = find_duplicate_groups(&files, &self.strategy());
+        if self.quarantine_entry.text().is_empty() {
+            if let Some(first_dir) = self.base_dirs().first() { self.quarantine_entry.set_text(&first_dir.join("_quarantaine").to_string_lossy()); }
+        }
+
+        while let Some(child) = self.groups_list.first_child() { self.groups_list.remove(&child); }
+        let total_wasted: u64 = groups.iter().map(|g| g.wasted_bytes).sum();
+        self.summary_label.set_text(&if groups.is_empty() {
+            "Aucun doublon dans les résultats.".to_string()
+        } else {
+            format!("{} groupe(s) de doublons — {} récupérables", groups.len(), format_size(total_wasted))
+        });
+        let rows: Vec<GroupRow> = groups.into_iter().map(|group| self.build_group_row(group, &files)).collect();
+        *self.state.borrow_mut() = ViewState { files, rows };
+    }
+
+    fn build_group_row(&self, group: DuplicateGroup, files: &[FileAnalysis]) -> GroupRow {
+        let row = GtkBox::new(Orientation::Vertical, 2);
+        row.set_margin_top(6); row.set_margin_bottom(6);
+        let header = format!("{} fichiers identiques ({} chacun) — {} récupérables", group.members.len(), format_size(group.file_size), format_size(group.wasted_bytes));
+        let check = CheckButton::builder().label(header).active(false).css_classes(vec!["heading".to_string()]).build();
+        row.append(&check);
+
+        let mut keeper_radios: Vec<(usize, CheckButton)> = Vec::new();
+        for &member in &group.members {
+            let path = files[member].full_original_path.clone();
+            let member_hbox = GtkBox::new(Orientation::Horizontal, 6);
+            member_hbox.set_margin_start(24);
+            let radio = CheckButton::builder().active(member == group.keeper).tooltip_text("Fichier conservé").build();
+            if let Some((_, first)) = keeper_radios.first() { radio.set_group(Some(first)); }
+            let picture = Picture::builder().content_fit(ContentFit::Contain).can_shrink(true).width_request(LIST_THUMBNAIL_SIZE as i32).height_request(LIST_THUMBNAIL_SIZE as i32).build();
+            self.thumbnail_loader.request(path.clone(), LIST_THUMBNAIL_SIZE, clone!(#[weak] picture, move |texture: Option<&Texture>| picture.set_paintable(texture)));
+            let label = Label::builder().label(path.to_string_lossy()).halign(Align::Start).hexpand(true).selectable(true).build();
+            member_hbox.append(&radio); member_hbox.append(&picture); member_hbox.append(&label);
+            row.append(&member_hbox);
+            keeper_radios.push((member, radio));
+        }
+        self.groups_list.append(&row);
+        GroupRow { group, check, keeper_radios }
+    }
+
+    fn set_all_checked(&self, checked: bool) {
+        for row in &self.state.borrow().rows { row.check.set_active(checked); }
+    }
+
+    fn show_alert(&self, message: &str, detail: &str) {
+        let window = self.root.root().and_then(|r| r.downcast::<Window>().ok());
+        AlertDialog::builder().message(message).detail(detail).modal(true).build().show(window.as_ref());
+    }
+
+    /// Applique l'action choisie aux groupes cochés, avec le fichier conservé choisi pour chacun,
+    /// après confirmation ; la vérification des hashes et les déplacements se font hors du fil de l'interface.
+    fn apply(&self) {
+        let groups: Vec<DuplicateGroup> = self.state.borrow().rows.iter().filter(|row| row.check.is_active()).map(GroupRow::group_with_keeper).collect();
+        if groups.is_empty() {
+            self.show_alert("Aucun groupe coché.", "");
+            return;
+        }
+        let (action, description) = match self.action_dropdown.selected() {
+            0 => {
+                let dir = self.quarantine_entry.text();
+                if dir.trim().is_empty() { self.show_alert("Indiquez un dossier de quarantaine.", ""); return; }
+                let description = format!("déplacés en quarantaine dans {}", dir.trim());
+                (ResolutionAction::Quarantine(PathBuf::from(dir.trim())), description)
+            }
+            1 => (ResolutionAction::Hardlink, "remplacés par des liens physiques vers le fichier conservé".to_string()),
+            _ => (ResolutionAction::Trash, "mis à la corbeille de l'application".to_string()),
+        };
+        let file_count: usize = groups.iter().map(|g| g.members.len() - 1).sum();
+        let total_size: u64 = groups.iter().map(|g| g.wasted_bytes).sum();
+        let confirm = AlertDialog::builder()
+            .message(format!("Appliquer « {} » aux groupes cochés ?", ACTION_LABELS[self.action_dropdown.selected() as usize]))
+            .detail(format!("{} groupe(s) : {} fichier(s) ({}) seront {}. Un fichier est conservé par groupe ; l'opération est annulable depuis l'historique.", groups.len(), file_count, format_size(total_size), description))
+            .buttons(["Annuler", "Appliquer"])
+            .cancel_button(0).default_button(0).modal(true)
+            .build();
+        let window = self.root.root().and_then(|r| r.downcast::<Window>().ok());
+        let view = self.clone();
+        confirm.choose(window.as_ref(), gio::Cancellable::NONE, move |response| {
+            if response == Ok(1) { view.resolve(groups, action); }
+        });
+    }
+
+    fn resolve(&self, groups: Vec<DuplicateGroup>, action: ResolutionAction) {
+        let journal = match Journal::open_default() {
+            Ok(journal) => journal,
+            Err(e) => { self.show_alert("Journal indisponible", &e.to_string()); return; }
+        };
+        let files = self.state.borrow().files.clone();
+        let base_dirs = self.base_dirs();
+        self.root.set_sensitive(false);
+        self.summary_label.set_text("Traitement des doublons en cours...");
+        let view = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let is_trash_or_link = matches!(action, ResolutionAction::Trash | ResolutionAction::Hardlink);
+            let result = gio::spawn_blocking(move || resolve_duplicates(&files, &groups, &action, &base_dirs, &journal)).await;
+            view.root.set_sensitive(true);
+            let report = match result {
+                Ok(report) => report,
+                Err(_) => { view.show_alert("Traitement des doublons interrompu", ""); view.refresh(); return; }
+            };
+            remove_paths_from_model(&view.results_model, &report.removed_paths, &view.removed_items);
+            let mut detail = format!("{} fichier(s) traité(s), {} déplacés", report.resolved, format_size(report.moved_bytes));
+            if is_trash_or_link {
+                detail.push_str("\nL'espace sera libéré en vidant la corbeille (« Vider la corbeille... »).");
+            }
+            if !report.errors.is_empty() {
+                detail.push_str(&format!("\n\n{} erreur(s) :\n", report.errors.len()));
+                detail.push_str(&report.errors.iter().take(10).cloned().collect::<Vec<_>>().join("\n"));
+            }
+            view.show_alert(if report.errors.is_empty() { "Doublons traités" } else { "Doublons traités avec des erreurs" }, &detail);
+            view.refresh();
+        });
+    }
+
+    /// Vide la corbeille de l'application après confirmation (suppression définitive).
+    fn empty_trash(&self) {
+        let journal = match Journal::open_default() {
+            Ok(journal) => journal,
+            Err(e) => { self.show_alert("Journal indisponible", &e.to_string()); return; }
+        };
+        let (count, size) = match trash_usage(&journal) {
+            Ok(usage) => usage,
+            Err(e) => { self.show_alert("Corbeille illisible", &e); return; }
+        };
+        if count == 0 { self.show_alert("La corbeille est vide.", ""); return; }
+        let confirm = AlertDialog::builder()
+            .message("Vider la corbeille ?")
+            .detail(format!("{} fichier(s), {} seront supprimés définitivement. Ces opérations ne pourront plus être annulées.", count, format_size(size)))
+            .buttons(["Annuler", "Vider la corbeille"])
+            .cancel_button(0).default_button(0).modal(true)
+            .build();
+        let window = self.root.root().and_then(|r| r.downcast::<Window>().ok());
+        let view = self.clone();
+        confirm.choose(window.as_ref(), gio::Cancellable::NONE, move |response| {
+            if response != Ok(1) { return; }
+            view.root.set_sensitive(false);
+            view.summary_label.set_text("Vidage de la corbeille en cours...");
+            glib::MainContext::default().spawn_local(async move {
+                // Suppressions hors du fil de l'interface, comme le traitement des doublons
+                let result = gio::spawn_blocking(move || empty_trash(&journal)).await;
+                view.root.set_sensitive(true);
+                match result {
+                    Ok(Ok(report)) => {
+                        let mut detail = format!("{} fichier(s) supprimé(s), {} libérés", report.deleted, format_size(report.freed_bytes));
+                        if !report.errors.is_empty() {
+                            detail.push_str(&format!("\n\n{} erreur(s) :\n", report.errors.len()));
+                            detail.push_str(&report.errors.iter().take(10).cloned().collect::<Vec<_>>().join("\n"));
+                        }
+                        view.show_alert("Corbeille vidée", &detail);
+                    }
+                    Ok(Err(e)) => view.show_alert("Corbeille non vidée", &e),
+                    Err(_) => view.show_alert("Vidage de la corbeille interrompu", ""),
+                }
+                view.refresh();
+            });
+        });
+    }
+}
+
+/// Retire du modèle les éléments dont le fichier a été déplacé (remis en place si l'opération est annulée).
+pub fn remove_paths_from_model(results_model: &ListStore, paths: &[PathBuf], removed_items: &RemovedItems) {
+    if paths.is_empty() { return; }
+    let mut index = results_model.n_items();
+    while index > 0 {
+        index -= 1;
+        if let Some(item) = results_model.item(index).and_then(|o| o.downcast::<FileDataItem>().ok()) {
+            if paths.contains(&item.full_original_path()) {
+                results_model.remove(index);
+                removed_items.remember(item);
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// timeshot_gui/src/exif_panel.rs
+
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Box as GtkBox, Button, Label, ListBox, Orientation, PolicyType, ScrolledWindow, SearchEntry,
+    SelectionMode,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use timeshot_core::exif::model::grouped_fields;
+use timeshot_core::types::FileAnalysis;
+
+/// Panneau latéral affichant toutes les métadonnées du fichier sélectionné, par catégorie.
+#[derive(Clone)]
+pub struct ExifPanel {
+    root: GtkBox,
+    title_label: Label,
+    search_entry: SearchEntry,
+    fields_list: ListBox,
+    current: Rc<RefCell<Option<FileAnalysis>>>,
+}
+
+impl ExifPanel {
+    pub fn new() -> Self {
+        let root = GtkBox::new(Orientation::Vertical, 6);
+        root.set_margin_start(6); root.set_width_request(280);
+        let title_label = Label::builder().label("Aucun fichier sélectionné").halign(Align::Start).wrap(true).css_classes(vec!["heading".to_string()]).build();
+        let search_entry = SearchEntry::builder().placeholder_text("Rechercher un champ").build();
+        let fields_list = ListBox::new();
+        fields_list.set_selection_mode(SelectionMode::None);
+        let scrolled = ScrolledWindow::builder().hscrollbar_policy(PolicyType::Never).vscrollbar_policy(PolicyType::Automatic).child(&fields_list).vexpand(true).build();
+        let copy_button = Button::builder().label("Copier").tooltip_text("Copie les champs affichés dans le presse-papiers").build();
+        root.append(&title_label); root.append(&search_entry); root.append(&scrolled); root.append(&copy_button);
+
+        let panel = ExifPanel { root, title_label, search_entry, fields_list, current: Rc::new(RefCell::new(None)) };
+        let panel_search = panel.clone();
+        panel.search_entry.connect_search_changed(move |_| panel_search.refresh());
+        let panel_copy = panel.clone();
+        copy_button.connect_clicked(move |button| {
+            let text = panel_copy.visible_text();
+            if !text.is_empty() { button.clipboard().set_text(&text); }
+        });
+        panel
+    }
+
+    pub fn widget(&self) -> &GtkBox {
+        &self.root
+    }
+
+    /// Affiche les métadonnées de ce fichier (ou vide le panneau).
+    pub fn show(&self, analysis: Option<FileAnalysis>) {
+        let title = analysis.as_ref().map(|a| a.original_name.clone()).unwrap_or_else(|| "Aucun fichier sélectionné".to_string());
+        self.title_label.set_text(&title);
+        *self.current.borrow_mut() = analysis;
+        self.refresh();
+    }
+
+    /// Champs correspondant à la recherche, par catégorie.
+    fn visible_fields(&self) -> Vec<(&'static str, Vec<(String, String)>)> {
+        let query = self.search_entry.text().to_lowercase();
+        let current = self.current.borrow();
+        let Some(analysis) = current.as_ref() else { return Vec::new() };
+        grouped_fields(&analysis.exif)
+            .into_iter()
+            .map(|(category, entries)| {
+                let entries = entries
+                    .into_iter()
+                    .filter(|(name, value)| query.is_empty() || name.to_lowercase().contains(&query) || value.to_lowercase().contains(&query))
+                    .collect::<Vec<_>>();
+                (category.label(), entries)
+            })
+            .filter(|(_, entries)| !entries.is_empty())
+            .collect()
+    }
+
+    fn visible_text(&self) -> String {
+        let mut text = String::new();
+        for (category, entries) in self.visible_fields() {
+            text.push_str(&format!("[{}]\n", category));
+            for (name, value) in entries { text.push_str(&format!("{} : {}\n", name, value)); }
+        }
+        text
+    }
+
+    fn refresh(&self) {
+        while let Some(child) = self.fields_list.first_child() { self.fields_list.remove(&child); }
+        let groups = self.visible_fields();
+        if groups.is_empty() && self.current.borrow().is_some() {
+            self.fields_list.append(&Label::builder().label("Aucune métadonnée").halign(Align::Start).build());
+        }
+        for (category, entries) in groups {
+            let header = Label::builder().label(category).halign(Align::Start).margin_top(8).css_classes(vec!["heading".to_string()]).build();
+            self.fields_list.append(&header);
+            for (name, value) in entries {
+                let row = GtkBox::new(Orientation::Vertical, 0);
+                row.append(&Label::builder().label(&name).halign(Align::Start).css_classes(vec!["dim-label".to_string()]).build());
+                row.append(&Label::builder().label(&value).halign(Align::Start).wrap(true).selectable(true).build());
+                self.fields_list.append(&row);
+            }
+        }
+    }
+}
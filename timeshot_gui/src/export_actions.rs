@@ -0,0 +1,148 @@
+// timeshot_gui/src/export_actions.rs
+
+use crate::file_data_item::FileDataItem;
+use gtk4::gio::{self, ListModel};
+use gtk4::prelude::*;
+use gtk4::{AlertDialog, ApplicationWindow, FileDialog};
+use std::path::{Path, PathBuf};
+use timeshot_core::export::checksums::{checksum_file_name, export_checksums, ChecksumLayout};
+use timeshot_core::export::csv::{export_to_csv_with_options, CsvColumn, CsvOptions};
+use timeshot_core::export::html::export_to_html;
+use timeshot_core::export::json::export_to_json;
+use timeshot_core::export::ndjson::export_to_ndjson;
+use timeshot_core::export::session::{Session, SessionFile};
+use timeshot_core::hash::compute::HashAlgorithm;
+use timeshot_core::types::{AnalysisOptions, FileAnalysis};
+
+/// Formats proposés dans la liste déroulante (même ordre que `ExportFormat::ALL`).
+pub const EXPORT_FORMAT_LABELS: [&str; 9] = ["CSV", "CSV (;)", "JSON", "NDJSON", "HTML", "Session", "Sommes BLAKE3", "Sommes SHA-256", "Sommes XXH3"];
+
+#[derive(Debug, Clone, Copy)]
+enum ExportFormat { Csv, CsvSemicolon, Json, Ndjson, Html, Session, Checksums(HashAlgorithm) }
+
+impl ExportFormat {
+    const ALL: [ExportFormat; 9] = [
+        ExportFormat::Csv, ExportFormat::CsvSemicolon, ExportFormat::Json, ExportFormat::Ndjson,
+        ExportFormat::Html, ExportFormat::Session, ExportFormat::Checksums(HashAlgorithm::Blake3),
+        ExportFormat::Checksums(HashAlgorithm::Sha256), ExportFormat::Checksums(HashAlgorithm::Xxh3),
+    ];
+
+    fn default_file_name(self) -> String {
+        match self {
+            ExportFormat::Csv | ExportFormat::CsvSemicolon => "timeshot_export.csv".to_string(),
+            ExportFormat::Json => "timeshot_export.json".to_string(),
+            ExportFormat::Ndjson => "timeshot_export.ndjson".to_string(),
+            ExportFormat::Html => "timeshot_rapport.html".to_string(),
+            ExportFormat::Session => "timeshot_session.json".to_string(),
+            ExportFormat::Checksums(algorithm) => checksum_file_name(algorithm).to_string(),
+        }
+    }
+}
+
+/// Élément exporté : l'analyse, la case cochée et le nom saisi dans la liste.
+struct ExportItem {
+    analysis: FileAnalysis,
+    selected: bool,
+    edited_name: Option<String>,
+}
+
+/// Éléments à exporter : les éléments cochés parmi ceux affichés, sinon tous les éléments affichés.
+fn collect_items(visible_model: &ListModel) -> Vec<ExportItem> {
+    let mut items: Vec<ExportItem> = Vec::new();
+    for i in 0..visible_model.n_items() {
+        if let Some(item) = visible_model.item(i).and_then(|o| o.downcast::<FileDataItem>().ok()) {
+            if let Some(analysis) = item.analysis() {
+                let edited_name = item.property::<bool>("name-edited").then(|| item.property::<String>("proposed-name"));
+                items.push(ExportItem { analysis, selected: item.property::<bool>("selected"), edited_name });
+            }
+        }
+    }
+    if items.iter().any(|item| item.selected) {
+        items.retain(|item| item.selected);
+    }
+    items
+}
+
+/// Demande le fichier de destination puis exporte les éléments affichés (ou cochés) au format choisi.
+pub fn export_results(window: &ApplicationWindow, visible_model: &ListModel, format_index: u32, directories: Vec<PathBuf>, options: AnalysisOptions) {
+    let format = ExportFormat::ALL[(format_index as usize).min(ExportFormat::ALL.len() - 1)];
+    let items = collect_items(visible_model);
+    if items.is_empty() {
+        AlertDialog::builder().message("Aucun fichier à exporter.").modal(true).build().show(Some(window));
+        return;
+    }
+    let file_dialog = FileDialog::builder().title("Exporter les résultats").initial_name(format.default_file_name()).modal(true).build();
+    let window_clone = window.clone();
+    file_dialog.save(Some(window), gio::Cancellable::NONE, move |result| {
+        let path = match result {
+            Ok(file) => match file.path() { Some(path) => path, None => return },
+            Err(_) => return, // Dialogue annulé
+        };
+        let count = items.len();
+        let (message, detail) = match write_export(format, &path, items, directories, options) {
+            Ok(()) => (format!("{} fichier(s) exporté(s)", count), path.display().to_string()),
+            Err(e) => {
+                eprintln!("Attention : export impossible : {}", e);
+                ("Échec de l'export".to_string(), e)
+            }
+        };
+        AlertDialog::builder().message(message).detail(detail).modal(true).build().show(Some(&window_clone));
+    });
+}
+
+fn write_export(format: ExportFormat, path: &Path, items: Vec<ExportItem>, directories: Vec<PathBuf>, options: AnalysisOptions) -> Result<(), String> {
+    let path_str = path.to_string_lossy();
+    match format {
+        ExportFormat::Csv | ExportFormat::CsvSemicolon => {
+            // Chemin complet en tête : permet de réimporter le fichier modifié (renommage par CSV)
+            let mut columns = vec![CsvColumn::FullPath];
+            columns.extend(CsvColumn::default_columns());
+            let delimiter = if matches!(format, ExportFormat::CsvSemicolon) { b';' } else { b',' };
+            let csv_options = CsvOptions { columns, delimiter, ..Default::default() };
+            export_to_csv_with_options(&path_str, &analyses(items), &csv_options).map_err(|e| e.to_string())
+        }
+        ExportFormat::Json => export_to_json(&path_str, &analyses(items)).map_err(|e| e.to_string()),
+        ExportFormat::Ndjson => export_to_ndjson(&path_str, &analyses(items)).map_err(|e| e.to_string()),
+        ExportFormat::Html => export_to_html(&path_str, &analyses(items), "Rapport TimeShotRenamer").map_err(|e| e.to_string()),
+        ExportFormat::Session => {
+            let files = items
+                .into_iter()
+                .map(|item| SessionFile { selected: item.selected, edited_name: item.edited_name, ..SessionFile::new(item.analysis) })
+                .collect();
+            Session::new(directories, options, files).save(path)
+        }
+        // Les hashes de l'analyse sont réutilisés s'ils ont été calculés avec cet algorithme
+        ExportFormat::Checksums(algorithm) => {
+            let report = export_checksums(&analyses(items), algorithm, &ChecksumLayout::Global(path.to_path_buf()));
+            if report.errors.is_empty() { Ok(()) } else { Err(report.errors.join("\n")) }
+        }
+    }
+}
+
+/// Analyses avec le nom saisi dans la liste à la place du nom généré.
+fn analyses(items: Vec<ExportItem>) -> Vec<FileAnalysis> {
+    items
+        .into_iter()
+        .map(|item| FileAnalysis { new_name: item.edited_name.or(item.analysis.new_name.clone()), ..item.analysis })
+        .collect()
+}
+
+/// Demande une session sauvegardée (export "Session") et la transmet à `on_loaded` pour remplacer
+/// les résultats affichés ; un échec de lecture est signalé sans toucher à la liste.
+pub fn import_session(window: &ApplicationWindow, on_loaded: impl Fn(Session) + 'static) {
+    let file_dialog = FileDialog::builder().title("Importer une session").modal(true).build();
+    let window_clone = window.clone();
+    file_dialog.open(Some(window), gio::Cancellable::NONE, move |result| {
+        let path = match result {
+            Ok(file) => match file.path() { Some(path) => path, None => return },
+            Err(_) => return, // Dialogue annulé
+        };
+        match Session::load(&path) {
+            Ok(session) => on_loaded(session),
+            Err(e) => {
+                eprintln!("Attention : import de session impossible : {}", e);
+                AlertDialog::builder().message("Échec de l'import de la session").detail(e).modal(true).build().show(Some(&window_clone));
+            }
+        }
+    });
+}
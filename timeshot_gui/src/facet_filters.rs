@@ -0,0 +1,145 @@
+// timeshot_gui/src/facet_filters.rs
+
+use crate::file_data_item::FileDataItem;
+use gtk4::gio::ListStore;
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Box as GtkBox, Button, CheckButton, CustomFilter, Entry, FilterChange, Label, MenuButton, Orientation,
+    PolicyType, Popover, ScrolledWindow,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use timeshot_core::facets::{facet_counts, parse_filter_date, Facet, FacetFilter};
+use timeshot_core::types::FileAnalysis;
+
+/// Filtres par facettes : plage de dates et un bouton par facette, dont la liste des valeurs
+/// (avec le nombre de fichiers de chacune) est recalculée à l'ouverture sur les résultats actuels.
+#[derive(Clone)]
+pub struct FacetFilters {
+    root: GtkBox,
+    date_from_entry: Entry,
+    date_to_entry: Entry,
+    facet_buttons: Vec<(Facet, MenuButton)>,
+    results_model: ListStore,
+    filter: CustomFilter,
+    state: Rc<RefCell<FacetFilter>>,
+}
+
+impl FacetFilters {
+    pub fn new(results_model: &ListStore) -> Self {
+        let root = GtkBox::new(Orientation::Vertical, 5);
+        let dates_hbox = GtkBox::new(Orientation::Horizontal, 6);
+        let date_from_entry = Entry::builder().placeholder_text("AAAA-MM-JJ").width_chars(12).build();
+        let date_to_entry = Entry::builder().placeholder_text("AAAA-MM-JJ").width_chars(12).build();
+        dates_hbox.append(&Label::new(Some("Date du"))); dates_hbox.append(&date_from_entry);
+        dates_hbox.append(&Label::new(Some("au"))); dates_hbox.append(&date_to_entry);
+        dates_hbox.append(&Label::builder().label("(fichiers sans date masqués si une borne est saisie)").css_classes(vec!["dim-label".to_string()]).build());
+
+        let facets_hbox = GtkBox::new(Orientation::Horizontal, 6);
+        let facet_buttons: Vec<(Facet, MenuButton)> = Facet::ALL
+            .into_iter()
+            .map(|facet| {
+                let button = MenuButton::builder().label(facet.label()).popover(&Popover::new()).build();
+                facets_hbox.append(&button);
+                (facet, button)
+            })
+            .collect();
+        let reset_button = Button::with_label("Réinitialiser");
+        facets_hbox.append(&reset_button);
+        root.append(&dates_hbox); root.append(&facets_hbox);
+
+        let state = Rc::new(RefCell::new(FacetFilter::default()));
+        let state_filter = state.clone();
+        let filter = CustomFilter::new(move |obj| {
+            let state = state_filter.borrow();
+            if state.is_empty() { return true; }
+            obj.downcast_ref::<FileDataItem>().and_then(|item| item.with_analysis(|analysis| state.matches(analysis))).unwrap_or(true)
+        });
+
+        let facets = FacetFilters { root, date_from_entry, date_to_entry, facet_buttons, results_model: results_model.clone(), filter, state };
+        for entry in [&facets.date_from_entry, &facets.date_to_entry] {
+            let facets_dates = facets.clone();
+            entry.connect_changed(move |_| facets_dates.update_dates());
+        }
+        for (facet, button) in &facets.facet_buttons {
+            let facets_popover = facets.clone();
+            let facet = *facet;
+            if let Some(popover) = button.popover() { popover.connect_show(move |popover| facets_popover.fill_popover(facet, popover)); }
+        }
+        let facets_reset = facets.clone();
+        reset_button.connect_clicked(move |_| facets_reset.reset());
+        facets
+    }
+
+    pub fn widget(&self) -> &GtkBox {
+        &self.root
+    }
+
+    /// Filtre à combiner avec les autres filtres de la liste.
+    pub fn filter(&self) -> &CustomFilter {
+        &self.filter
+    }
+
+    fn update_dates(&self) {
+        let mut bounds = Vec::new();
+        for entry in [&self.date_from_entry, &self.date_to_entry] {
+            // Borne invalide : signalée et ignorée
+            let bound = match parse_filter_date(&entry.text()) {
+                Ok(date) => { entry.remove_css_class("error"); entry.set_tooltip_text(None); date }
+                Err(e) => { entry.add_css_class("error"); entry.set_tooltip_text(Some(&e)); None }
+            };
+            bounds.push(bound);
+        }
+        {
+            let mut state = self.state.borrow_mut();
+            state.date_from = bounds[0];
+            state.date_to = bounds[1];
+        }
+        self.filter.changed(FilterChange::Different);
+    }
+
+    /// Liste des valeurs de la facette avec leur nombre de fichiers ; les valeurs cochées absentes
+    /// des résultats actuels restent affichées (0) pour pouvoir être décochées.
+    fn fill_popover(&self, facet: Facet, popover: &Popover) {
+        let analyses: Vec<FileAnalysis> = (0..self.results_model.n_items())
+            .filter_map(|i| self.results_model.item(i).and_then(|o| o.downcast::<FileDataItem>().ok()))
+            .filter_map(|item| item.analysis())
+            .collect();
+        let mut counts = facet_counts(&analyses, facet);
+        if let Some(selected) = self.state.borrow().selected.get(&facet) {
+            for value in selected {
+                if !counts.iter().any(|(v, _)| v == value) { counts.push((value.clone(), 0)); }
+            }
+        }
+
+        let list_box = GtkBox::new(Orientation::Vertical, 4);
+        list_box.set_margin_top(6); list_box.set_margin_bottom(6); list_box.set_margin_start(6); list_box.set_margin_end(6);
+        if counts.is_empty() { list_box.append(&Label::builder().label("Aucun fichier analysé").halign(Align::Start).build()); }
+        for (value, count) in counts {
+            let check = CheckButton::builder().label(format!("{} ({})", value, count)).active(self.state.borrow().is_selected(facet, &value)).build();
+            let facets = self.clone();
+            check.connect_toggled(move |check| {
+                facets.state.borrow_mut().set_selected(facet, &value, check.is_active());
+                facets.update_button_label(facet);
+                facets.filter.changed(FilterChange::Different);
+            });
+            list_box.append(&check);
+        }
+        let scrolled = ScrolledWindow::builder().hscrollbar_policy(PolicyType::Never).vscrollbar_policy(PolicyType::Automatic).propagate_natural_height(true).max_content_height(400).child(&list_box).build();
+        popover.set_child(Some(&scrolled));
+    }
+
+    /// Libellé du bouton avec le nombre de valeurs cochées (ex: "Appareil (2)").
+    fn update_button_label(&self, facet: Facet) {
+        let Some((_, button)) = self.facet_buttons.iter().find(|(f, _)| *f == facet) else { return };
+        let selected = self.state.borrow().selected_count(facet);
+        button.set_label(&if selected == 0 { facet.label().to_string() } else { format!("{} ({})", facet.label(), selected) });
+    }
+
+    fn reset(&self) {
+        *self.state.borrow_mut() = FacetFilter::default();
+        self.date_from_entry.set_text(""); self.date_to_entry.set_text("");
+        for (facet, _) in &self.facet_buttons { self.update_button_label(*facet); }
+        self.filter.changed(FilterChange::Different);
+    }
+}
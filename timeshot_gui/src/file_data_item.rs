@@ -6,6 +6,7 @@ use gtk4::subclass::prelude::*;
 use once_cell::sync::Lazy;
 use std::cell::{Cell, RefCell};
 use std::path::PathBuf; // <-- Ajout PathBuf
+use timeshot_core::stream::DuplicateUpdate;
 use timeshot_core::types::FileAnalysis;
 
 // Module interne
@@ -16,14 +17,20 @@ mod imp {
     pub struct FileDataItem {
         // !! NOUVEAU CHAMP INTERNE (non exposé comme propriété GObject) !!
         pub full_original_path: RefCell<PathBuf>,
+        // Analyse complète d'origine (non exposée comme propriété GObject)
+        pub analysis: RefCell<Option<FileAnalysis>>,
         // Propriétés GObject existantes
         pub original_name: RefCell<String>,
         pub proposed_name: RefCell<Option<String>>,
+        // Nom proposé modifié à la main dans la liste
+        pub name_edited: Cell<bool>,
         pub folder_name: RefCell<String>,
         pub date_taken: RefCell<Option<String>>,
         pub is_duplicate: Cell<bool>,
+        pub is_probable_duplicate: Cell<bool>,
         pub selected: Cell<bool>,
         pub file_hash: RefCell<Option<String>>,
+        pub hash_error: RefCell<Option<String>>,
     }
 
     #[glib::object_subclass]
@@ -39,11 +46,14 @@ mod imp {
                 vec![
                     ParamSpecString::builder("original-name").build(),
                     ParamSpecString::builder("proposed-name").build(),
+                    ParamSpecBoolean::builder("name-edited").build(),
                     ParamSpecString::builder("folder-name").build(),
                     ParamSpecString::builder("date-taken").build(),
                     ParamSpecBoolean::builder("is-duplicate").build(),
+                    ParamSpecBoolean::builder("is-probable-duplicate").build(),
                     ParamSpecBoolean::builder("selected").build(),
                     ParamSpecString::builder("file-hash").build(),
+                    ParamSpecString::builder("hash-error").build(),
                 ]
             });
             PROPERTIES.as_ref()
@@ -53,11 +63,14 @@ mod imp {
              match pspec.name() {
                 "original-name" => self.original_name.borrow().to_value(),
                 "proposed-name" => self.proposed_name.borrow().as_deref().unwrap_or("").to_value(),
+                "name-edited" => self.name_edited.get().to_value(),
                 "folder-name" => self.folder_name.borrow().to_value(),
                 "date-taken" => self.date_taken.borrow().as_deref().unwrap_or("").to_value(),
                 "is-duplicate" => self.is_duplicate.get().to_value(),
+                "is-probable-duplicate" => self.is_probable_duplicate.get().to_value(),
                 "selected" => self.selected.get().to_value(),
                 "file-hash" => self.file_hash.borrow().as_deref().unwrap_or("").to_value(),
+                "hash-error" => self.hash_error.borrow().as_deref().unwrap_or("").to_value(),
                 _ => unimplemented!(),
             }
         }
@@ -68,10 +81,13 @@ mod imp {
                 // Les autres ne sont normalement pas settés depuis l'UI pour l'instant
                  "original-name" => { if let Ok(name) = value.get() { *self.original_name.borrow_mut() = name;}}
                  "proposed-name" => { if let Ok(name) = value.get() { *self.proposed_name.borrow_mut() = name;}}
+                 "name-edited" => { if let Ok(edited) = value.get() { self.name_edited.set(edited);}}
                  "folder-name" => { if let Ok(name) = value.get() { *self.folder_name.borrow_mut() = name;}}
                  "date-taken" => { if let Ok(date) = value.get() { *self.date_taken.borrow_mut() = date;}}
                  "is-duplicate" => { if let Ok(is_dup) = value.get() { self.is_duplicate.set(is_dup);}}
+                 "is-probable-duplicate" => { if let Ok(is_dup) = value.get() { self.is_probable_duplicate.set(is_dup);}}
                  "file-hash" => { if let Ok(hash) = value.get() { *self.file_hash.borrow_mut() = hash;}}
+                 "hash-error" => { if let Ok(error) = value.get() { *self.hash_error.borrow_mut() = error;}}
                 _ => unimplemented!(),
             }
         }
@@ -99,8 +115,11 @@ impl FileDataItem {
         *obj.imp().folder_name.borrow_mut() = analysis.folder_name.clone();
         *obj.imp().date_taken.borrow_mut() = date_str;
         obj.imp().is_duplicate.set(analysis.is_duplicate);
+        obj.imp().is_probable_duplicate.set(analysis.is_probable_duplicate);
         obj.imp().selected.set(false); // Non sélectionné par défaut
         *obj.imp().file_hash.borrow_mut() = analysis.file_hash.clone();
+        *obj.imp().hash_error.borrow_mut() = analysis.hash_error.clone();
+        *obj.imp().analysis.borrow_mut() = Some(analysis.clone());
 
         obj // Retourne l'objet construit et rempli
     }
@@ -109,4 +128,42 @@ impl FileDataItem {
     pub fn full_original_path(&self) -> PathBuf {
         self.imp().full_original_path.borrow().clone()
     }
+
+    // Applique le résultat de la passe finale d'une analyse en flux
+    pub fn apply_duplicate_update(&self, update: &DuplicateUpdate) {
+        self.imp().is_duplicate.set(update.is_duplicate);
+        self.imp().is_probable_duplicate.set(update.is_probable_duplicate);
+        *self.imp().file_hash.borrow_mut() = update.file_hash.clone();
+        *self.imp().hash_error.borrow_mut() = update.hash_error.clone();
+        if let Some(analysis) = self.imp().analysis.borrow_mut().as_mut() { update.apply(analysis); }
+    }
+
+    // Nom proposé par l'analyse (avant toute modification manuelle)
+    pub fn generated_name(&self) -> Option<String> {
+        self.imp().analysis.borrow().as_ref().and_then(|a| a.new_name.clone())
+    }
+
+    // Remplace le nom proposé par un nom saisi ; `None` revient au nom généré
+    pub fn set_edited_name(&self, name: Option<String>) {
+        let edited = name.is_some();
+        let name = name.or_else(|| self.generated_name());
+        self.set_property("proposed-name", name);
+        self.set_property("name-edited", edited);
+    }
+
+    // Nouveau nom généré (autre modèle) ; un nom saisi à la main est conservé
+    pub fn set_generated_name(&self, name: Option<String>) {
+        if let Some(analysis) = self.imp().analysis.borrow_mut().as_mut() { analysis.new_name = name.clone(); }
+        if !self.imp().name_edited.get() { self.set_property("proposed-name", name); }
+    }
+
+    // Accès à l'analyse sans copie (tri, affichage des colonnes)
+    pub fn with_analysis<R>(&self, f: impl FnOnce(&FileAnalysis) -> R) -> Option<R> {
+        self.imp().analysis.borrow().as_ref().map(f)
+    }
+
+    // Analyse core complète ayant servi à créer l'élément
+    pub fn analysis(&self) -> Option<FileAnalysis> {
+        self.imp().analysis.borrow().clone()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,161 @@
+// timeshot_gui/src/history_dialog.rs
+
+use crate::file_data_item::FileDataItem;
+use gtk4::gio::{self, ListStore};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, AlertDialog, ApplicationWindow, Box as GtkBox, Button, CheckButton, Expander, Label, ListBox,
+    Orientation, PolicyType, ScrolledWindow, SelectionMode, Window,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+use timeshot_core::journal::entry::{JournalEntry, Operation};
+use timeshot_core::journal::store::{Journal, JournalBatch};
+use timeshot_core::journal::undo::{undo_entries, UndoReport};
+
+/// Éléments retirés de la liste après renommage ou traitement des doublons (quarantaine, corbeille),
+/// par chemin d'origine, pour les y remettre si l'opération est annulée pendant la session.
+#[derive(Clone, Default)]
+pub struct RemovedItems(Rc<RefCell<HashMap<PathBuf, FileDataItem>>>);
+
+impl RemovedItems {
+    pub fn remember(&self, item: FileDataItem) {
+        self.0.borrow_mut().insert(item.full_original_path(), item);
+    }
+
+    /// Remet dans la liste les fichiers dont le renommage ou le déplacement a été défait ; renvoie leur nombre.
+    fn restore(&self, results_model: &ListStore, operations: &[Operation]) -> usize {
+        let mut restored = 0;
+        for operation in operations {
+            if let Operation::Rename { from, .. } | Operation::Quarantine { from, .. } | Operation::Trash { from, .. } = operation {
+                if let Some(item) = self.0.borrow_mut().remove(from) {
+                    results_model.append(&item);
+                    restored += 1;
+                }
+            }
+        }
+        restored
+    }
+}
+
+fn show_report(parent: &impl IsA<Window>, report: &UndoReport, restored: usize) {
+    let mut detail = format!("{} opération(s) annulée(s), {} fichier(s) remis dans la liste", report.undone, restored);
+    if !report.errors.is_empty() {
+        detail.push_str(&format!("\n\n{} erreur(s) :\n", report.errors.len()));
+        detail.push_str(&report.errors.iter().take(10).cloned().collect::<Vec<_>>().join("\n"));
+    }
+    let message = if report.errors.is_empty() { "Annulation terminée" } else { "Annulation incomplète" };
+    AlertDialog::builder().message(message).detail(detail).modal(true).build().show(Some(parent));
+}
+
+fn undo_and_restore(parent: &impl IsA<Window>, entries: &[JournalEntry], results_model: &ListStore, removed_items: &RemovedItems) {
+    let result = Journal::open_default().map_err(|e| e.to_string()).and_then(|journal| undo_entries(&journal, entries));
+    match result {
+        Ok(report) => {
+            let restored = removed_items.restore(results_model, &report.operations);
+            show_report(parent, &report, restored);
+        }
+        Err(e) => {
+            eprintln!("Attention : annulation impossible : {}", e);
+            AlertDialog::builder().message("Annulation impossible").detail(e).modal(true).build().show(Some(parent));
+        }
+    }
+}
+
+fn load_batches() -> Result<Vec<JournalBatch>, String> {
+    Journal::open_default().and_then(|journal| journal.batches()).map_err(|e| format!("Lecture du journal impossible : {}", e))
+}
+
+fn batch_title(batch: &JournalBatch) -> String {
+    let renames = batch.entries.iter().filter(|e| matches!(e.operation, Operation::Rename { .. })).count();
+    let kind = if renames > 0 { format!("{} renommage(s)", renames) } else { "Modifications".to_string() };
+    format!("{} — {}, {} opération(s) au total", batch.timestamp.format("%d/%m/%Y %H:%M:%S"), kind, batch.entries.len())
+}
+
+/// Annule le dernier lot contenant des renommages (avec les dates de fichiers appliquées dans ce lot).
+pub fn undo_last_rename(window: &ApplicationWindow, results_model: &ListStore, removed_items: &RemovedItems) {
+    let batch = match load_batches() {
+        Ok(batches) => batches.into_iter().find(JournalBatch::has_renames),
+        Err(e) => { AlertDialog::builder().message("Annulation impossible").detail(e).modal(true).build().show(Some(window)); return; }
+    };
+    let Some(batch) = batch else {
+        AlertDialog::builder().message("Aucun renommage à annuler.").modal(true).build().show(Some(window));
+        return;
+    };
+    let confirm = AlertDialog::builder()
+        .message("Annuler le dernier renommage ?")
+        .detail(batch_title(&batch))
+        .buttons(["Non", "Annuler le renommage"])
+        .cancel_button(0).default_button(1).modal(true)
+        .build();
+    let window_clone = window.clone();
+    let results_model = results_model.clone();
+    let removed_items = removed_items.clone();
+    confirm.choose(Some(window), gio::Cancellable::NONE, move |response| {
+        if response == Ok(1) { undo_and_restore(&window_clone, &batch.entries, &results_model, &removed_items); }
+    });
+}
+
+/// Fenêtre d'historique : lots du journal (plus récent en premier), annulables en entier ou fichier par fichier.
+pub fn show_history_dialog(window: &ApplicationWindow, results_model: &ListStore, removed_items: &RemovedItems) {
+    let dialog = Window::builder().title("Historique des opérations").transient_for(window).modal(true).default_width(800).default_height(600).build();
+    let root_vbox = GtkBox::new(Orientation::Vertical, 6);
+    root_vbox.set_margin_top(10); root_vbox.set_margin_bottom(10); root_vbox.set_margin_start(10); root_vbox.set_margin_end(10);
+    let batches_list = ListBox::new();
+    batches_list.set_selection_mode(SelectionMode::None);
+    let scrolled = ScrolledWindow::builder().hscrollbar_policy(PolicyType::Automatic).vscrollbar_policy(PolicyType::Automatic).child(&batches_list).vexpand(true).build();
+    root_vbox.append(&Label::builder().label("Opérations enregistrées dans le journal").halign(Align::Start).css_classes(vec!["heading".to_string()]).build());
+    root_vbox.append(&scrolled);
+    dialog.set_child(Some(&root_vbox));
+    populate_history(&dialog, &batches_list, results_model, removed_items);
+    dialog.present();
+}
+
+fn populate_history(dialog: &Window, batches_list: &ListBox, results_model: &ListStore, removed_items: &RemovedItems) {
+    while let Some(child) = batches_list.first_child() { batches_list.remove(&child); }
+    let batches = match load_batches() {
+        Ok(batches) => batches,
+        Err(e) => { batches_list.append(&Label::new(Some(&e))); return; }
+    };
+    if batches.is_empty() {
+        batches_list.append(&Label::builder().label("Aucune opération annulable.").halign(Align::Start).build());
+        return;
+    }
+    for batch in batches {
+        let entries_vbox = GtkBox::new(Orientation::Vertical, 2);
+        entries_vbox.set_margin_start(24);
+        let checks: Vec<(CheckButton, JournalEntry)> = batch
+            .entries
+            .iter()
+            .map(|entry| {
+                let check = CheckButton::builder().label(entry.operation.describe()).tooltip_text(entry.operation.path().to_string_lossy()).build();
+                entries_vbox.append(&check);
+                (check, entry.clone())
+            })
+            .collect();
+        let buttons_hbox = GtkBox::new(Orientation::Horizontal, 6);
+        let undo_batch_button = Button::with_label("Annuler le lot");
+        let undo_selection_button = Button::with_label("Annuler la sélection");
+        buttons_hbox.append(&undo_batch_button); buttons_hbox.append(&undo_selection_button);
+        entries_vbox.append(&buttons_hbox);
+        let expander = Expander::builder().label(batch_title(&batch)).child(&entries_vbox).build();
+        batches_list.append(&expander);
+
+        let checks = Rc::new(checks);
+        for (button, selection_only) in [(undo_batch_button, false), (undo_selection_button, true)] {
+            let checks = checks.clone();
+            let dialog = dialog.clone();
+            let batches_list = batches_list.clone();
+            let results_model = results_model.clone();
+            let removed_items = removed_items.clone();
+            button.connect_clicked(move |_| {
+                let entries: Vec<JournalEntry> = checks.iter().filter(|(check, _)| !selection_only || check.is_active()).map(|(_, entry)| entry.clone()).collect();
+                if entries.is_empty() { return; }
+                undo_and_restore(&dialog, &entries, &results_model, &removed_items);
+                populate_history(&dialog, &batches_list, &results_model, &removed_items);
+            });
+        }
+    }
+}
@@ -1,8 +1,18 @@
 // timeshot_gui/src/main.rs
 
 mod ui;
+mod columns;
 mod file_data_item; // Assurez-vous que ce module est bien déclaré
 mod search_handler;
+mod duplicates_view;
+mod export_actions;
+mod history_dialog;
+mod rename_confirm_dialog;
+mod exif_panel;
+mod facet_filters;
+mod name_edit;
+mod template_editor;
+mod thumbnails;
 
 use gtk4::prelude::*;
 use gtk4::Application;
@@ -0,0 +1,128 @@
+// timeshot_gui/src/name_edit.rs
+
+use crate::file_data_item::FileDataItem;
+use gtk4::gio::ListStore;
+use gtk4::glib::{self, clone};
+use gtk4::prelude::*;
+use gtk4::{EditableLabel, ListItem, SignalListItemFactory};
+use std::path::Path;
+use timeshot_core::renamer::plan::validate_file_name;
+
+/// Résultat de la vérification d'un nom saisi.
+pub enum NameCheck {
+    Valid,
+    /// Nom utilisable mais à vérifier (extension modifiée, cible déjà prise...).
+    Warning(String),
+    /// Nom refusé.
+    Invalid(String),
+}
+
+/// Vérifie un nom proposé pour `item` : caractères interdits, cible identique à celle d'un autre
+/// fichier de la liste ou déjà présente sur le disque, changement d'extension.
+pub fn check_proposed_name(item: &FileDataItem, name: &str, results_model: &ListStore) -> NameCheck {
+    if let Err(e) = validate_file_name(name) {
+        return NameCheck::Invalid(e);
+    }
+    let original_path = item.full_original_path();
+    let parent = original_path.parent().unwrap_or(Path::new(""));
+    let mut warnings: Vec<String> = Vec::new();
+
+    let extension = |n: &str| Path::new(n).extension().map(|e| e.to_string_lossy().to_lowercase());
+    let original_name = item.property::<String>("original-name");
+    if extension(name) != extension(&original_name) {
+        warnings.push(format!(
+            "Extension modifiée (.{} → .{})",
+            extension(&original_name).unwrap_or_default(),
+            extension(name).unwrap_or_default()
+        ));
+    }
+
+    let same_target = (0..results_model.n_items())
+        .filter_map(|i| results_model.item(i).and_then(|o| o.downcast::<FileDataItem>().ok()))
+        .filter(|other| other != item && other.property::<String>("proposed-name") == name)
+        .any(|other| other.full_original_path().parent() == Some(parent));
+    if same_target {
+        warnings.push("Un autre fichier du même dossier a déjà ce nom proposé".to_string());
+    }
+    let target = parent.join(name);
+    let case_only = original_name.to_lowercase() == name.to_lowercase();
+    if target.exists() && !case_only {
+        warnings.push(format!("'{}' existe déjà sur le disque", name));
+    }
+
+    if warnings.is_empty() { NameCheck::Valid } else { NameCheck::Warning(warnings.join("\n")) }
+}
+
+fn show_check(label: &EditableLabel, check: &NameCheck) {
+    label.remove_css_class("name-warning"); label.remove_css_class("name-error");
+    match check {
+        NameCheck::Valid => label.set_tooltip_text(None),
+        NameCheck::Warning(message) => { label.add_css_class("name-warning"); label.set_tooltip_text(Some(message)); }
+        NameCheck::Invalid(message) => { label.add_css_class("name-error"); label.set_tooltip_text(Some(message)); }
+    }
+}
+
+fn bound_item(list_item: &ListItem) -> Option<FileDataItem> {
+    list_item.item().and_then(|o| o.downcast::<FileDataItem>().ok())
+}
+
+/// Colonne "Nom Proposé" modifiable : vérification à chaque frappe, nom refusé si invalide (raison en infobulle),
+/// nom vide = retour au nom généré. Les lignes modifiées sont marquées (classe `edited-name`).
+pub fn create_proposed_name_factory(results_model: &ListStore) -> SignalListItemFactory {
+    let factory = SignalListItemFactory::new();
+    let model_setup = results_model.clone();
+    factory.connect_setup(move |_factory, list_item| {
+        let list_item = list_item.downcast_ref::<ListItem>().expect("Needs ListItem");
+        let label = EditableLabel::builder().hexpand(true).build();
+        list_item.set_child(Some(&label));
+
+        // Vérification en direct pendant la saisie
+        label.connect_changed(clone!(#[weak] list_item, #[weak(rename_to = model)] model_setup, move |label| {
+            if !label.is_editing() { return; }
+            if let Some(item) = bound_item(&list_item) {
+                let text = label.text();
+                if !text.trim().is_empty() { show_check(label, &check_proposed_name(&item, text.trim(), &model)); }
+            }
+        }));
+        // Fin de saisie : enregistre le nom dans l'élément (conservé quel que soit le filtrage)
+        label.connect_notify_local(Some("editing"), clone!(#[weak] list_item, #[weak(rename_to = model)] model_setup, move |label, _| {
+            if label.is_editing() { return; }
+            let Some(item) = bound_item(&list_item) else { return };
+            let text = label.text().trim().to_string();
+            let current = item.property::<String>("proposed-name");
+            let mut rejected = None;
+            if text.is_empty() {
+                item.set_edited_name(None);
+            } else if text != current {
+                match check_proposed_name(&item, &text, &model) {
+                    NameCheck::Invalid(message) => rejected = Some(format!("Nom « {} » refusé : {}", text, message)),
+                    _ => item.set_edited_name(Some(text)),
+                }
+            }
+            label.set_text(&item.property::<String>("proposed-name"));
+            refresh_marks(label, &item, &model);
+            // Nom refusé : l'ancien nom est rétabli, la raison reste visible jusqu'au prochain affichage
+            if let Some(message) = rejected { show_check(label, &NameCheck::Invalid(message)); }
+        }));
+    });
+
+    let model_bind = results_model.clone();
+    factory.connect_bind(move |_factory, list_item| {
+        let list_item = list_item.downcast_ref::<ListItem>().expect("Needs ListItem");
+        let Some(item) = bound_item(list_item) else { return };
+        let Some(label) = list_item.child().and_then(|c| c.downcast::<EditableLabel>().ok()) else { return };
+        label.set_text(&item.property::<String>("proposed-name"));
+        refresh_marks(&label, &item, &model_bind);
+    });
+    factory
+}
+
+fn refresh_marks(label: &EditableLabel, item: &FileDataItem, model: &ListStore) {
+    if item.property::<bool>("name-edited") {
+        label.add_css_class("edited-name");
+        show_check(label, &check_proposed_name(item, &item.property::<String>("proposed-name"), model));
+    } else {
+        label.remove_css_class("edited-name");
+        show_check(label, &NameCheck::Valid);
+    }
+}
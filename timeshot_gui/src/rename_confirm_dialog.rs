@@ -0,0 +1,111 @@
+// timeshot_gui/src/rename_confirm_dialog.rs
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::{
+    Align, ApplicationWindow, Box as GtkBox, Button, Label, ListBox, Orientation, PolicyType, ScrolledWindow,
+    SelectionMode, Window,
+};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use timeshot_core::renamer::plan::{common_affixes, RenameOperation, RenamePlan};
+
+/// Nom avec la partie modifiée mise en évidence (balisage Pango).
+fn highlighted_name(name: &str, prefix: usize, suffix: usize, color: &str) -> String {
+    let changed = &name[prefix..name.len() - suffix];
+    if changed.is_empty() { return glib::markup_escape_text(name).to_string(); }
+    format!(
+        "{}<span background=\"{}\" foreground=\"black\"><b>{}</b></span>{}",
+        glib::markup_escape_text(&name[..prefix]), color,
+        glib::markup_escape_text(changed),
+        glib::markup_escape_text(&name[name.len() - suffix..])
+    )
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+fn operation_row(operation: &RenameOperation) -> GtkBox {
+    let row = GtkBox::new(Orientation::Vertical, 2);
+    row.set_margin_top(3); row.set_margin_bottom(3); row.set_margin_start(6); row.set_margin_end(6);
+    let (old_name, new_name) = (file_name(&operation.from), file_name(&operation.to));
+    let (prefix, suffix) = common_affixes(&old_name, &new_name);
+    let markup = format!("{}  →  {}", highlighted_name(&old_name, prefix, suffix, "#f5b7b1"), highlighted_name(&new_name, prefix, suffix, "#abebc6"));
+    let names_label = Label::builder().use_markup(true).label(markup).halign(Align::Start).selectable(true).wrap(true).wrap_mode(gtk4::pango::WrapMode::WordChar).build();
+    names_label.set_tooltip_text(operation.from.parent().map(|p| p.to_string_lossy().into_owned()).as_deref());
+    row.append(&names_label);
+    if let Some(conflict) = &operation.conflict {
+        row.append(&Label::builder().label(format!("Ignoré : {}", conflict)).halign(Align::Start).css_classes(vec!["hash-error".to_string()]).build());
+    } else if let Some(warning) = operation.warning() {
+        row.append(&Label::builder().label(format!("Attention : {}", warning)).halign(Align::Start).css_classes(vec!["probable-duplicate".to_string()]).build());
+    }
+    row
+}
+
+/// Chemins d'origine des opérations en conflit.
+fn conflicting_sources(plan: &RenamePlan) -> HashSet<PathBuf> {
+    plan.conflicts().map(|op| op.from.clone()).collect()
+}
+
+/// Liste le plan (ancien → nouveau nom, conflits et avertissements) et n'appelle `on_confirm`
+/// qu'après validation explicite ; les opérations en conflit ne seront pas appliquées.
+/// Le plan est revérifié au moment de la validation : si de nouveaux conflits sont apparus
+/// entre-temps, il est présenté à nouveau au lieu d'être appliqué.
+pub fn confirm_rename_plan(window: &ApplicationWindow, renames: Vec<(PathBuf, PathBuf)>, skipped_count: usize, on_confirm: impl Fn(RenamePlan) + 'static) {
+    let plan = RenamePlan::new(renames.clone());
+    show_plan(window, plan, renames, skipped_count, Rc::new(on_confirm));
+}
+
+fn show_plan(window: &ApplicationWindow, plan: RenamePlan, renames: Vec<(PathBuf, PathBuf)>, skipped_count: usize, on_confirm: Rc<dyn Fn(RenamePlan)>) {
+    let conflicts = plan.conflicts().count();
+    let warnings = plan.warnings().count();
+    let applicable = plan.operations.len() - conflicts;
+
+    let dialog = Window::builder().title("Confirmer le renommage").transient_for(window).modal(true).default_width(900).default_height(600).build();
+    let root_vbox = GtkBox::new(Orientation::Vertical, 6);
+    root_vbox.set_margin_top(10); root_vbox.set_margin_bottom(10); root_vbox.set_margin_start(10); root_vbox.set_margin_end(10);
+    let mut summary = format!("{} fichier(s) seront renommés", applicable);
+    if conflicts > 0 { summary.push_str(&format!(", {} ignoré(s) pour conflit", conflicts)); }
+    if warnings > 0 { summary.push_str(&format!(", {} avertissement(s)", warnings)); }
+    if skipped_count > 0 { summary.push_str(&format!(", {} sélectionné(s) sans changement de nom", skipped_count)); }
+    root_vbox.append(&Label::builder().label(summary).halign(Align::Start).css_classes(vec!["heading".to_string()]).build());
+
+    let operations_list = ListBox::new();
+    operations_list.set_selection_mode(SelectionMode::None);
+    // Conflits et avertissements en tête pour être vus sans défiler
+    let mut operations: Vec<&RenameOperation> = plan.operations.iter().collect();
+    operations.sort_by_key(|op| (op.conflict.is_none(), op.warning().is_none()));
+    for operation in operations { operations_list.append(&operation_row(operation)); }
+    let scrolled = ScrolledWindow::builder().hscrollbar_policy(PolicyType::Never).vscrollbar_policy(PolicyType::Automatic).child(&operations_list).vexpand(true).build();
+    root_vbox.append(&scrolled);
+
+    let buttons_hbox = GtkBox::new(Orientation::Horizontal, 6);
+    buttons_hbox.set_halign(Align::End);
+    let cancel_button = Button::with_label("Annuler");
+    let confirm_button = Button::with_label(&format!("Renommer {} fichier(s)", applicable));
+    confirm_button.add_css_class("destructive-action");
+    confirm_button.set_sensitive(applicable > 0);
+    buttons_hbox.append(&cancel_button); buttons_hbox.append(&confirm_button);
+    root_vbox.append(&buttons_hbox);
+    dialog.set_child(Some(&root_vbox));
+
+    let dialog_cancel = dialog.clone();
+    cancel_button.connect_clicked(move |_| dialog_cancel.close());
+    let dialog_confirm = dialog.clone();
+    let window = window.clone();
+    let shown_conflicts = conflicting_sources(&plan);
+    confirm_button.connect_clicked(move |_| {
+        dialog_confirm.close();
+        // Le disque a pu changer pendant la relecture
+        let fresh_plan = RenamePlan::new(renames.clone());
+        if conflicting_sources(&fresh_plan).is_subset(&shown_conflicts) {
+            on_confirm(fresh_plan);
+        } else {
+            show_plan(&window, fresh_plan, renames.clone(), skipped_count, on_confirm.clone());
+        }
+    });
+    cancel_button.grab_focus();
+    dialog.present();
+}
@@ -0,0 +1,168 @@
+// timeshot_gui/src/template_editor.rs
+
+use crate::file_data_item::FileDataItem;
+use gtk4::gio::{ListModel, ListStore};
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::{Align, Box as GtkBox, Button, Entry, FlowBox, Label, Orientation, SelectionMode};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+use timeshot_core::renamer::generator::{apply_name_template, generate_filename};
+use timeshot_core::renamer::template::{NameCounters, NameTemplate, DEFAULT_TEMPLATE, TEMPLATE_TOKENS};
+use timeshot_core::types::FileAnalysis;
+
+/// Nombre de lignes affichées dans l'aperçu.
+const PREVIEW_ROWS: u32 = 5;
+
+/// Éditeur du modèle de nommage : saisie, boutons d'insertion des jetons, aperçu des noms produits
+/// pour les premières lignes affichées, et application à toute la liste sans nouvelle analyse.
+#[derive(Clone)]
+pub struct TemplateEditor {
+    root: GtkBox,
+    entry: Entry,
+    error_label: Label,
+    preview_label: Label,
+    apply_button: Button,
+    results_model: ListStore,
+    visible_model: ListModel,
+    refresh_pending: Rc<Cell<bool>>,
+}
+
+impl TemplateEditor {
+    pub fn new(results_model: &ListStore, visible_model: &ListModel) -> Self {
+        let root = GtkBox::new(Orientation::Vertical, 6);
+        root.set_margin_start(10); root.set_margin_end(10); root.set_margin_top(5); root.set_margin_bottom(5);
+        let entry_hbox = GtkBox::new(Orientation::Horizontal, 6);
+        let entry = Entry::builder().text(DEFAULT_TEMPLATE).hexpand(true).build();
+        let reset_button = Button::builder().label("Par défaut").tooltip_text(DEFAULT_TEMPLATE).build();
+        let apply_button = Button::builder().label("Appliquer").tooltip_text("Recalcule les noms proposés de toute la liste avec ce modèle (les noms saisis à la main sont conservés)").build();
+        entry_hbox.append(&Label::new(Some("Modèle :"))); entry_hbox.append(&entry); entry_hbox.append(&reset_button); entry_hbox.append(&apply_button);
+
+        let tokens_box = FlowBox::builder().selection_mode(SelectionMode::None).max_children_per_line(16).column_spacing(4).row_spacing(4).build();
+        for (token, description) in TEMPLATE_TOKENS {
+            let button = Button::builder().label(token).tooltip_text(description).build();
+            let entry_token = entry.clone();
+            button.connect_clicked(move |_| {
+                let mut position = entry_token.position();
+                entry_token.insert_text(token, &mut position);
+                entry_token.set_position(position);
+                entry_token.grab_focus_without_selecting();
+            });
+            tokens_box.insert(&button, -1);
+        }
+        let error_label = Label::builder().halign(Align::Start).visible(false).css_classes(vec!["template-error".to_string()]).build();
+        let preview_label = Label::builder().halign(Align::Start).selectable(true).css_classes(vec!["monospace".to_string()]).build();
+        root.append(&entry_hbox); root.append(&tokens_box); root.append(&error_label);
+        root.append(&Label::builder().label("Aperçu :").halign(Align::Start).css_classes(vec!["dim-label".to_string()]).build());
+        root.append(&preview_label);
+
+        let editor = TemplateEditor {
+            root, entry, error_label, preview_label, apply_button,
+            results_model: results_model.clone(), visible_model: visible_model.clone(), refresh_pending: Rc::new(Cell::new(false)),
+        };
+        let editor_changed = editor.clone();
+        editor.entry.connect_changed(move |_| editor_changed.refresh());
+        let entry_reset = editor.entry.clone();
+        reset_button.connect_clicked(move |_| entry_reset.set_text(DEFAULT_TEMPLATE));
+        let editor_apply = editor.clone();
+        editor.apply_button.connect_clicked(move |_| editor_apply.apply());
+        // L'aperçu suit la liste (analyse en cours, filtres) ; recalcul regroupé une fois par boucle
+        let editor_items = editor.clone();
+        editor.visible_model.connect_items_changed(move |_, _, _, _| editor_items.schedule_refresh());
+        editor.refresh();
+        editor
+    }
+
+    pub fn widget(&self) -> &GtkBox {
+        &self.root
+    }
+
+    /// Modèle saisi, à transmettre aux options d'analyse.
+    pub fn template_text(&self) -> String {
+        self.entry.text().to_string()
+    }
+
+    /// Remplace le modèle saisi (ex: session importée) ; l'aperçu suit.
+    pub fn set_template_text(&self, text: &str) {
+        self.entry.set_text(text);
+    }
+
+    fn schedule_refresh(&self) {
+        if self.refresh_pending.replace(true) { return; }
+        let editor = self.clone();
+        glib::idle_add_local_once(move || {
+            editor.refresh_pending.set(false);
+            editor.refresh();
+        });
+    }
+
+    /// Noms produits par le modèle pour toute la liste (ordre d'analyse, comme le renommeur) ;
+    /// réservé à "Appliquer", l'aperçu se limitant aux premières lignes (`preview_names`).
+    fn generated_names(&self, template: &NameTemplate) -> HashMap<PathBuf, String> {
+        let mut analyses: Vec<FileAnalysis> = (0..self.results_model.n_items())
+            .filter_map(|i| self.results_model.item(i).and_then(|o| o.downcast::<FileDataItem>().ok()))
+            .filter_map(|item| item.analysis())
+            .collect();
+        apply_name_template(&mut analyses, template);
+        analyses.into_iter().filter_map(|a| a.new_name.map(|name| (a.full_original_path, name))).collect()
+    }
+
+    /// Noms des lignes de l'aperçu. Suffixes et compteurs ne dépendant que des fichiers précédents
+    /// (ordre d'analyse), le rendu s'arrête à la dernière ligne affichée, sans copier les analyses.
+    fn preview_names(&self, template: &NameTemplate, visible: &[FileDataItem]) -> Vec<Option<String>> {
+        let positions: Vec<Option<u32>> = visible.iter().map(|item| self.results_model.find(item)).collect();
+        let Some(last) = positions.iter().flatten().max().copied() else { return vec![None; visible.len()] };
+        let mut counters = NameCounters::default();
+        let mut rendered: HashMap<u32, String> = HashMap::new();
+        for i in 0..=last {
+            let Some(item) = self.results_model.item(i).and_then(|o| o.downcast::<FileDataItem>().ok()) else { continue };
+            if let Some(name) = item.with_analysis(|analysis| generate_filename(analysis, template, &mut counters)) {
+                if positions.contains(&Some(i)) { rendered.insert(i, name); }
+            }
+        }
+        positions.iter().map(|position| position.and_then(|p| rendered.remove(&p))).collect()
+    }
+
+    fn refresh(&self) {
+        let template = match NameTemplate::parse(&self.template_text()) {
+            Ok(template) => template,
+            Err(e) => {
+                self.error_label.set_text(&e); self.error_label.set_visible(true);
+                self.entry.add_css_class("error"); self.apply_button.set_sensitive(false);
+                return;
+            }
+        };
+        self.error_label.set_visible(false); self.entry.remove_css_class("error"); self.apply_button.set_sensitive(true);
+
+        let visible: Vec<FileDataItem> = (0..self.visible_model.n_items().min(PREVIEW_ROWS))
+            .filter_map(|i| self.visible_model.item(i).and_then(|o| o.downcast::<FileDataItem>().ok()))
+            .collect();
+        if visible.is_empty() {
+            self.preview_label.set_text("Aucun fichier analysé");
+            return;
+        }
+        let names = self.preview_names(&template, &visible);
+        let lines: Vec<String> = visible
+            .iter()
+            .zip(&names)
+            .map(|(item, new_name)| format!("{}  →  {}", item.property::<String>("original-name"), new_name.as_deref().unwrap_or("-")))
+            .collect();
+        self.preview_label.set_text(&lines.join("\n"));
+    }
+
+    /// Applique le modèle à toute la liste, sans nouvelle analyse.
+    fn apply(&self) {
+        let Ok(template) = NameTemplate::parse(&self.template_text()) else { return };
+        let names = self.generated_names(&template);
+        let count = self.results_model.n_items();
+        for i in 0..count {
+            if let Some(item) = self.results_model.item(i).and_then(|o| o.downcast::<FileDataItem>().ok()) {
+                item.set_generated_name(names.get(&item.full_original_path()).cloned());
+            }
+        }
+        // Les cellules ne suivent pas les propriétés : on force leur réaffichage
+        self.results_model.items_changed(0, count, count);
+    }
+}
@@ -0,0 +1,119 @@
+// timeshot_gui/src/thumbnails.rs
+
+use crate::file_data_item::FileDataItem;
+use gtk4::gdk::{MemoryFormat, MemoryTexture, Texture};
+use gtk4::glib::{self, clone};
+use gtk4::prelude::*;
+use gtk4::{gio, ContentFit, ListItem, Picture, SignalListItemFactory};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::rc::Rc;
+use timeshot_core::exif::thumbnail::{load_thumbnail, Thumbnail};
+
+/// Taille des vignettes de la colonne "Aperçu".
+pub const LIST_THUMBNAIL_SIZE: u32 = 64;
+/// Taille de l'aperçu affiché pour la sélection.
+pub const PREVIEW_SIZE: u32 = 480;
+/// Au-delà, le cache est vidé (évite de garder en mémoire les vignettes de toute une photothèque).
+const MAX_CACHED: usize = 2000;
+
+type Callback = Box<dyn FnOnce(Option<&Texture>)>;
+/// Clé de cache : chemin du fichier et taille demandée.
+type ThumbnailKey = (PathBuf, u32);
+
+/// Chargement des vignettes dans un thread de travail, avec cache par (chemin, taille).
+/// Les demandes identiques en cours sont regroupées ; les échecs sont mémorisés (pas de nouvel essai).
+#[derive(Clone, Default)]
+pub struct ThumbnailLoader {
+    cache: Rc<RefCell<HashMap<ThumbnailKey, Option<Texture>>>>,
+    pending: Rc<RefCell<HashMap<ThumbnailKey, Vec<Callback>>>>,
+}
+
+impl ThumbnailLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appelle `callback` avec la vignette (immédiatement si elle est en cache), `None` si illisible.
+    pub fn request(&self, path: PathBuf, size: u32, callback: impl FnOnce(Option<&Texture>) + 'static) {
+        let key = (path, size);
+        let cached = self.cache.borrow().get(&key).cloned();
+        if let Some(texture) = cached {
+            callback(texture.as_ref());
+            return;
+        }
+        let mut pending = self.pending.borrow_mut();
+        if let Some(callbacks) = pending.get_mut(&key) {
+            callbacks.push(Box::new(callback));
+            return;
+        }
+        pending.insert(key.clone(), vec![Box::new(callback)]);
+        drop(pending);
+
+        let loader = self.clone();
+        glib::MainContext::default().spawn_local(async move {
+            let (path, size) = key.clone();
+            let result = gio::spawn_blocking(move || load_thumbnail(&path, size)).await;
+            let texture = match result {
+                Ok(Ok(thumbnail)) => Some(to_texture(thumbnail)),
+                // Vidéos, RAW... : pas d'aperçu, sans avertissement (cas courant)
+                Ok(Err(_)) | Err(_) => None,
+            };
+            {
+                let mut cache = loader.cache.borrow_mut();
+                if cache.len() >= MAX_CACHED { cache.clear(); }
+                cache.insert(key.clone(), texture.clone());
+            }
+            let callbacks = loader.pending.borrow_mut().remove(&key).unwrap_or_default();
+            for callback in callbacks { callback(texture.as_ref()); }
+        });
+    }
+
+    /// Oublie les vignettes de ces fichiers (après renommage ou suppression).
+    pub fn forget(&self, paths: &HashSet<PathBuf>) {
+        self.cache.borrow_mut().retain(|(path, _), _| !paths.contains(path));
+    }
+}
+
+fn to_texture(thumbnail: Thumbnail) -> Texture {
+    let stride = thumbnail.width as usize * 4;
+    let bytes = glib::Bytes::from_owned(thumbnail.rgba);
+    MemoryTexture::new(thumbnail.width as i32, thumbnail.height as i32, MemoryFormat::R8g8b8a8, &bytes, stride).upcast()
+}
+
+fn load_into(loader: &ThumbnailLoader, picture: &Picture, list_item: &ListItem) {
+    let Some(item) = list_item.item().and_then(|o| o.downcast::<FileDataItem>().ok()) else { return };
+    loader.request(item.full_original_path(), LIST_THUMBNAIL_SIZE, clone!(#[weak] picture, #[weak] list_item, move |texture| {
+        // La ligne a pu être réutilisée pour un autre fichier entre-temps
+        if list_item.item().as_ref() == Some(item.upcast_ref()) { picture.set_paintable(texture); }
+    }));
+}
+
+/// Colonne "Aperçu" : vignettes chargées en arrière-plan, uniquement pour les lignes affichées
+/// (la colonne masquée ne déclenche aucun chargement).
+pub fn create_thumbnail_factory(loader: &ThumbnailLoader) -> SignalListItemFactory {
+    let factory = SignalListItemFactory::new();
+    let loader_setup = loader.clone();
+    factory.connect_setup(move |_factory, list_item| {
+        let list_item = list_item.downcast_ref::<ListItem>().expect("Needs ListItem");
+        let picture = Picture::builder().content_fit(ContentFit::Contain).can_shrink(true).height_request(LIST_THUMBNAIL_SIZE as i32).build();
+        list_item.set_child(Some(&picture));
+        let loader = loader_setup.clone();
+        picture.connect_map(clone!(#[weak] list_item, move |picture| {
+            if picture.paintable().is_none() { load_into(&loader, picture, &list_item); }
+        }));
+    });
+    let loader_bind = loader.clone();
+    factory.connect_bind(move |_factory, list_item| {
+        let list_item = list_item.downcast_ref::<ListItem>().expect("Needs ListItem");
+        let Some(picture) = list_item.child().and_then(|c| c.downcast::<Picture>().ok()) else { return };
+        picture.set_paintable(None::<&Texture>);
+        if picture.is_mapped() { load_into(&loader_bind, &picture, list_item); }
+    });
+    factory.connect_unbind(move |_factory, list_item| {
+        let list_item = list_item.downcast_ref::<ListItem>().expect("Needs ListItem");
+        if let Some(picture) = list_item.child().and_then(|c| c.downcast::<Picture>().ok()) { picture.set_paintable(None::<&Texture>); }
+    });
+    factory
+}
@@ -1,28 +1,47 @@
 // timeshot_gui/src/ui.rs
 
+use crate::columns::{create_column_chooser, create_extra_column, property_sorter, status_sorter, ExtraColumn};
+use crate::duplicates_view::DuplicatesView;
+use crate::exif_panel::ExifPanel;
+use crate::facet_filters::FacetFilters;
+use crate::export_actions::{export_results, import_session, EXPORT_FORMAT_LABELS};
 use crate::file_data_item::FileDataItem;
+use crate::history_dialog::{show_history_dialog, undo_last_rename, RemovedItems};
+use crate::name_edit::create_proposed_name_factory;
+use crate::rename_confirm_dialog::confirm_rename_plan;
+use crate::template_editor::TemplateEditor;
+use crate::thumbnails::{create_thumbnail_factory, ThumbnailLoader, PREVIEW_SIZE};
 use gtk4::gio::ListStore;
 use gtk4::glib::{self, clone};
 use gtk4::prelude::*;
 use gtk4::{
-    Align, Application, ApplicationWindow, Box as GtkBox, Button, CheckButton, ColumnView,
-    ColumnViewColumn, CssProvider, DialogFlags, Entry, Expander, FileChooserAction,
+    Align, AlertDialog, Application, ApplicationWindow, Box as GtkBox, Button, CheckButton, ColumnView,
+    ColumnViewColumn, ContentFit, CssProvider, DialogFlags, DropDown, Entry, Expander, FileChooserAction,
     FileChooserDialog, FilterListModel, Label, ListBox, ListItem, MessageDialog, MessageType,
-    MultiSelection, Orientation, PolicyType, ResponseType, ScrolledWindow, SelectionMode,
-    SignalListItemFactory, ButtonsType, StringObject, CustomFilter, FilterChange,
+    MultiSelection, Notebook, Orientation, Paned, Picture, PolicyType, ResponseType, ScrolledWindow, SelectionMode,
+    SignalListItemFactory, SortListModel, ButtonsType, SpinButton, StringObject, CustomFilter, EveryFilter, FilterChange,
 };
 use gtk4::pango;
 use once_cell::sync::Lazy; // <-- Import pour la Regex statique
 use regex::Regex;
 use std::{
     collections::HashSet,
-    fs,
     path::PathBuf,
     rc::Rc as StdRc,
     cell::RefCell as StdRefCell,
+    sync::mpsc::TryRecvError,
+    time::Duration,
 };
 
-use timeshot_core::analyze_multiple_directories;
+use chrono::{FixedOffset, NaiveDateTime};
+use timeshot_core::hash::perceptual::DEFAULT_SIMILARITY_THRESHOLD;
+use timeshot_core::journal::store::{new_batch_id, Journal};
+use timeshot_core::renamer::apply::apply_rename_plan;
+use timeshot_core::renamer::timestamps::{set_file_times_from_dates, set_file_times_in_batch, FileTimesTarget};
+use timeshot_core::cache::{delete_cache_file, AnalysisCache};
+use timeshot_core::stream::{analyze_streaming, AnalysisEvent};
+use timeshot_core::types::{AnalysisOptions, CacheMode};
+use timeshot_core::renamer::template::DEFAULT_TEMPLATE;
 
 // --- Structure pour l'état des filtres ---
 #[derive(Default)]
@@ -35,6 +54,43 @@ struct FilterState {
     hide_if_name_has_date: bool, // <-- Nouvel état pour le filtre de date
 }
 
+/// Options d'analyse choisies dans la fenêtre, partagées par la recherche et l'export de session.
+#[derive(Clone)]
+struct AnalysisControls {
+    recursive_checkbox: CheckButton,
+    similar_checkbox: CheckButton,
+    similarity_spin: SpinButton,
+    cache_checkbox: CheckButton,
+    template_editor: TemplateEditor,
+}
+
+impl AnalysisControls {
+    fn options(&self) -> AnalysisOptions {
+        AnalysisOptions {
+            recursive: self.recursive_checkbox.is_active(),
+            similarity_threshold: if self.similar_checkbox.is_active() { Some(self.similarity_spin.value_as_int() as u32) } else { None },
+            cache: if self.cache_checkbox.is_active() { CacheMode::Enabled } else { CacheMode::Disabled },
+            name_template: Some(self.template_editor.template_text()),
+            ..Default::default()
+        }
+    }
+
+    /// Reprend les options d'une session importée.
+    fn set_options(&self, options: &AnalysisOptions) {
+        self.recursive_checkbox.set_active(options.recursive);
+        self.similar_checkbox.set_active(options.similarity_threshold.is_some());
+        if let Some(threshold) = options.similarity_threshold { self.similarity_spin.set_value(threshold as f64); }
+        self.cache_checkbox.set_active(options.cache != CacheMode::Disabled);
+        self.template_editor.set_template_text(options.name_template.as_deref().unwrap_or(DEFAULT_TEMPLATE));
+    }
+}
+
+// Date de prise de vue et décalage horaire EXIF
+type CaptureDate = (NaiveDateTime, Option<FixedOffset>);
+
+// Nombre max d'événements d'analyse traités par passage de la boucle GTK
+const STREAM_EVENTS_PER_TICK: usize = 200;
+
 // --- Regex compilée une seule fois pour vérifier la présence de date ---
 static RE_DATE_IN_NAME: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?:^|\b|[_.-])(\d{4}[-_]?\d{2}[-_]?\d{2})(?:$|\b|[_.-])")
@@ -75,7 +131,13 @@ fn create_status_label_factory() -> SignalListItemFactory {
         let file_item = item_option.unwrap().downcast::<FileDataItem>().expect("Needs FileDataItem");
         let label_option = list_item_gtk.child(); if label_option.is_none() { return; }
         let label = label_option.unwrap().downcast::<Label>().expect("Needs Label");
-        if file_item.property::<bool>("is-duplicate") { label.set_text("Doublon"); label.add_css_class("duplicate"); } else { label.set_text(""); label.remove_css_class("duplicate"); }
+        label.remove_css_class("duplicate"); label.remove_css_class("probable-duplicate"); label.remove_css_class("hash-error");
+        label.set_tooltip_text(None);
+        let hash_error = file_item.property::<String>("hash-error");
+        if !hash_error.is_empty() { label.set_text("Erreur de lecture"); label.set_tooltip_text(Some(&hash_error)); label.add_css_class("hash-error"); }
+        else if file_item.property::<bool>("is-duplicate") { label.set_text("Doublon"); label.add_css_class("duplicate"); }
+        else if file_item.property::<bool>("is-probable-duplicate") { label.set_text("Doublon probable"); label.add_css_class("probable-duplicate"); }
+        else { label.set_text(""); }
      });
      factory
 }
@@ -112,7 +174,7 @@ pub fn build_ui(app: &Application) {
     // --- CSS ---
     let provider = CssProvider::new();
     // Utiliser load_from_string("") à terme
-    provider.load_from_data("label.duplicate { color: orange; font-style: italic; } columnview > header > button > label { font-weight: bold; } button.destructive-action { background-color: #e74c3c; color: white; }");
+    provider.load_from_data("label.duplicate { color: orange; font-style: italic; } label.probable-duplicate { color: #b8860b; font-style: italic; } label.hash-error { color: #c0392b; } label.template-error { color: #c0392b; } editablelabel.edited-name { font-weight: bold; color: #2471a3; } editablelabel.name-warning { color: #d35400; } editablelabel.name-error { color: #c0392b; } columnview > header > button > label { font-weight: bold; } button.destructive-action { background-color: #e74c3c; color: white; }");
     gtk4::style_context_add_provider_for_display( &gtk4::gdk::Display::default().expect("Could not connect to a display."), &provider, gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION );
 
     // --- Fenêtre principale ---
@@ -146,7 +208,15 @@ pub fn build_ui(app: &Application) {
     let add_dir_button = Button::with_label("Ajouter");
     let remove_dir_button = Button::with_label("Retirer");
     let recursive_checkbox = CheckButton::with_label("Récursif"); recursive_checkbox.set_active(false);
+    let similar_checkbox = CheckButton::builder().label("Doublons probables").tooltip_text("Compare le contenu visuel des images (plus lent) pour repérer les copies redimensionnées ou ré-encodées").active(false).build();
+    let similarity_spin = SpinButton::with_range(0.0, 32.0, 1.0); similarity_spin.set_value(DEFAULT_SIMILARITY_THRESHOLD as f64);
+    similarity_spin.set_tooltip_text(Some("Seuil de similarité (distance de Hamming, 0 = quasi identiques)")); similarity_spin.set_sensitive(false);
+    similar_checkbox.connect_toggled(clone!(#[weak] similarity_spin, move |check| { similarity_spin.set_sensitive(check.is_active()); }));
     dir_button_hbox.append(&add_dir_button); dir_button_hbox.append(&remove_dir_button); dir_button_hbox.append(&recursive_checkbox);
+    dir_button_hbox.append(&similar_checkbox); dir_button_hbox.append(&similarity_spin);
+    let cache_checkbox = CheckButton::builder().label("Cache").tooltip_text("Réutilise les analyses des fichiers inchangés (même taille et date de modification)").active(false).build();
+    let clear_cache_button = Button::with_label("Vider le cache");
+    dir_button_hbox.append(&cache_checkbox); dir_button_hbox.append(&clear_cache_button);
     dir_vbox.append(&dir_label); dir_vbox.append(&dir_scrolled_window); dir_vbox.append(&dir_button_hbox);
 
     // 2. Bouton Chercher
@@ -184,27 +254,99 @@ pub fn build_ui(app: &Application) {
         .label("Masquer si nom contient déjà AAAA-MM-JJ").tooltip_text("Masque les fichiers dont le nom original semble déjà contenir une date (YYYY-MM-DD, YYYY_MM_DD ou YYYYMMDD)")
         .active(false).halign(Align::Start).build();
     filter_grid.attach(&hide_if_name_has_date_check, 1, 3, 1, 1);
+    // Lignes 4-5: Plage de dates et facettes
+    let facet_filters = FacetFilters::new(&results_model);
+    filter_grid.attach(facet_filters.widget(), 0, 4, 2, 1);
     filter_expander.set_child(Some(&filter_grid));
 
-    // --- Création du ColumnView ---
-    let results_selection_model = MultiSelection::new(Some(filter_model.clone()));
-    let column_view = ColumnView::builder().model(&results_selection_model).show_column_separators(true).show_row_separators(true).build();
+    // --- Création du ColumnView (tri par clic sur les en-têtes : filtre -> tri -> sélection) ---
+    let column_view = ColumnView::builder().show_column_separators(true).show_row_separators(true).build();
+    let sort_model = SortListModel::new(Some(filter_model.clone()), column_view.sorter());
+    let results_selection_model = MultiSelection::new(Some(sort_model.clone()));
+    column_view.set_model(Some(&results_selection_model));
     // --- Définition des colonnes ---
     let check_factory = create_checkbox_factory(); let check_column = ColumnViewColumn::builder().title("✓").factory(&check_factory).fixed_width(40).resizable(false).build(); column_view.append_column(&check_column);
-    let orig_factory = create_label_factory("original-name", false, vec![]); let orig_column = ColumnViewColumn::builder().title("Nom Original").factory(&orig_factory).expand(true).resizable(true).build(); column_view.append_column(&orig_column);
-    let prop_factory = create_label_factory("proposed-name", false, vec![]); let prop_column = ColumnViewColumn::builder().title("Nom Proposé").factory(&prop_factory).expand(true).resizable(true).build(); column_view.append_column(&prop_column);
-    let date_factory = create_label_factory("date-taken", false, vec![]); let date_column = ColumnViewColumn::builder().title("Date Prise").factory(&date_factory).fixed_width(160).resizable(true).build(); column_view.append_column(&date_column);
-    let status_factory = create_status_label_factory(); let status_column = ColumnViewColumn::builder().title("Statut").factory(&status_factory).fixed_width(80).resizable(true).build(); column_view.append_column(&status_column);
+    let thumbnail_loader = ThumbnailLoader::new();
+    let thumb_factory = create_thumbnail_factory(&thumbnail_loader); let thumb_column = ColumnViewColumn::builder().title("Aperçu").factory(&thumb_factory).fixed_width(80).resizable(false).visible(false).build(); column_view.append_column(&thumb_column);
+    let orig_factory = create_label_factory("original-name", false, vec![]); let orig_column = ColumnViewColumn::builder().title("Nom Original").factory(&orig_factory).sorter(&property_sorter("original-name")).expand(true).resizable(true).build(); column_view.append_column(&orig_column);
+    let prop_factory = create_proposed_name_factory(&results_model); let prop_column = ColumnViewColumn::builder().title("Nom Proposé").factory(&prop_factory).sorter(&property_sorter("proposed-name")).expand(true).resizable(true).build(); column_view.append_column(&prop_column);
+    let date_factory = create_label_factory("date-taken", false, vec![]); let date_column = ColumnViewColumn::builder().title("Date Prise").factory(&date_factory).sorter(&property_sorter("date-taken")).fixed_width(160).resizable(true).build(); column_view.append_column(&date_column);
+    let status_factory = create_status_label_factory(); let status_column = ColumnViewColumn::builder().title("Statut").factory(&status_factory).sorter(&status_sorter()).fixed_width(80).resizable(true).build(); column_view.append_column(&status_column);
+    let extra_columns: Vec<(ExtraColumn, ColumnViewColumn)> = ExtraColumn::ALL.into_iter().map(|column| (column, create_extra_column(column))).collect();
+    for (_, column) in &extra_columns { column_view.append_column(column); }
+    let columns_button = create_column_chooser(&extra_columns);
+
+    // --- Modèle de nommage ---
+    let template_expander = Expander::new(Some("Modèle de nommage")); template_expander.set_margin_bottom(10);
+    let template_editor = TemplateEditor::new(&results_model, sort_model.upcast_ref());
+    template_expander.set_child(Some(template_editor.widget()));
+    let analysis_controls = AnalysisControls {
+        recursive_checkbox: recursive_checkbox.clone(),
+        similar_checkbox: similar_checkbox.clone(),
+        similarity_spin: similarity_spin.clone(),
+        cache_checkbox: cache_checkbox.clone(),
+        template_editor: template_editor.clone(),
+    };
     // --- ScrolledWindow pour ColumnView ---
     let results_scrolled_window = ScrolledWindow::builder().hscrollbar_policy(PolicyType::Automatic).vscrollbar_policy(PolicyType::Automatic).child(&column_view).vexpand(true).build();
     // --- Boutons d'action sous la liste ---
     let results_actions_hbox = GtkBox::new(Orientation::Horizontal, 6);
     let select_all_button = Button::with_label("Tout Sélectionner"); let deselect_all_button = Button::with_label("Tout Désélectionner"); let select_exif_button = Button::with_label("Sélectionner si Date EXIF");
     results_actions_hbox.append(&select_all_button); results_actions_hbox.append(&deselect_all_button); results_actions_hbox.append(&select_exif_button);
+    let duplicates_button = Button::builder().label("Doublons...").tooltip_text("Ouvre l'onglet des groupes de doublons").build(); results_actions_hbox.append(&duplicates_button);
+    let export_format_dropdown = DropDown::from_strings(&EXPORT_FORMAT_LABELS);
+    let export_button = Button::builder().label("Exporter...").tooltip_text("Exporte les fichiers cochés parmi ceux affichés, sinon tous les fichiers affichés").build();
+    results_actions_hbox.append(&export_format_dropdown); results_actions_hbox.append(&export_button);
+    let import_session_button = Button::builder().label("Importer une session...").tooltip_text("Recharge une session exportée (dossiers, options, résultats, sélection et noms modifiés) pour relire et appliquer son plan").build();
+    results_actions_hbox.append(&import_session_button);
+    let thumbnails_check = CheckButton::builder().label("Miniatures").tooltip_text("Affiche la colonne des vignettes (miniature EXIF si présente, sinon image décodée)").active(false).build();
+    thumbnails_check.connect_toggled(clone!(#[weak] thumb_column, move |check| thumb_column.set_visible(check.is_active())));
+    results_actions_hbox.append(&thumbnails_check); results_actions_hbox.append(&columns_button);
     results_actions_hbox.append(&GtkBox::builder().orientation(Orientation::Horizontal).hexpand(true).build()); // Spacer
+    let set_file_times_check = CheckButton::builder().label("Dater les fichiers").tooltip_text("Après renommage, applique la date de prise de vue comme date de modification des fichiers cochés, renommés ou non (annulable via le journal)").active(false).build();
+    results_actions_hbox.append(&set_file_times_check);
+    let undo_rename_button = Button::builder().label("Annuler le dernier renommage").tooltip_text("Remet les fichiers du dernier renommage à leur nom d'origine").build();
+    let history_button = Button::builder().label("Historique...").tooltip_text("Opérations enregistrées dans le journal, annulables par lot ou par fichier").build();
+    results_actions_hbox.append(&undo_rename_button); results_actions_hbox.append(&history_button);
     let rename_button = Button::with_label("Renommer Sélection"); rename_button.add_css_class("destructive-action"); results_actions_hbox.append(&rename_button);
     // --- Assemblage Zone Résultats ---
-    results_vbox.append(&filter_expander); results_vbox.append(&results_scrolled_window); results_vbox.append(&results_actions_hbox);
+    // --- Panneau latéral : aperçu et métadonnées du fichier sélectionné ---
+    let exif_panel = ExifPanel::new();
+    let preview_picture = Picture::builder().content_fit(ContentFit::Contain).can_shrink(true).height_request(240).margin_start(6).build();
+    let side_vbox = GtkBox::new(Orientation::Vertical, 6);
+    side_vbox.append(&preview_picture); side_vbox.append(exif_panel.widget());
+    let results_paned = Paned::builder().orientation(Orientation::Horizontal).start_child(&results_scrolled_window).end_child(&side_vbox).resize_end_child(false).shrink_end_child(false).vexpand(true).build();
+    let loader_preview = thumbnail_loader.clone();
+    results_selection_model.connect_selection_changed(clone!(#[strong] exif_panel, #[weak] preview_picture, move |selection, _, _| {
+        let selected = selection.selection();
+        let item = if selected.is_empty() { None } else { selection.item(selected.minimum()).and_then(|o| o.downcast::<FileDataItem>().ok()) };
+        preview_picture.set_paintable(None::<&gtk4::gdk::Texture>);
+        if let Some(item) = &item {
+            let path = item.full_original_path();
+            preview_picture.set_tooltip_text(Some(&path.to_string_lossy()));
+            // N'affiche l'aperçu que si la sélection n'a pas changé pendant le chargement
+            loader_preview.request(path.clone(), PREVIEW_SIZE, clone!(#[weak] preview_picture, move |texture| {
+                if preview_picture.tooltip_text().as_deref() == Some(&*path.to_string_lossy()) { preview_picture.set_paintable(texture); }
+            }));
+        } else {
+            preview_picture.set_tooltip_text(None);
+        }
+        exif_panel.show(item.and_then(|item| item.analysis()));
+    }));
+    let files_page_vbox = GtkBox::new(Orientation::Vertical, 6);
+    files_page_vbox.append(&filter_expander); files_page_vbox.append(&template_expander); files_page_vbox.append(&results_paned); files_page_vbox.append(&results_actions_hbox);
+    // --- Onglets : fichiers / groupes de doublons ---
+    // Fichiers retirés de la liste (renommés, doublons déplacés), remis en place par l'annulation
+    let removed_items = RemovedItems::default();
+    let duplicates_view = DuplicatesView::new(&results_model, &directory_store, &thumbnail_loader, &removed_items);
+    let results_notebook = Notebook::builder().vexpand(true).build();
+    results_notebook.append_page(&files_page_vbox, Some(&Label::new(Some("Fichiers"))));
+    let duplicates_page = results_notebook.append_page(duplicates_view.widget(), Some(&Label::new(Some("Doublons"))));
+    // Groupes recalculés à chaque ouverture de l'onglet (résultats modifiés entre-temps)
+    results_notebook.connect_switch_page(clone!(#[strong] duplicates_view, move |_, _, page| {
+        if page == duplicates_page { duplicates_view.refresh(); }
+    }));
+    results_vbox.append(&results_notebook);
 
     // --- Assemblage Final UI ---
     let root_vbox = GtkBox::new(Orientation::Vertical, 6);
@@ -255,12 +397,23 @@ pub fn build_ui(app: &Application) {
         }
     });
 
+    // --- Logique Bouton "Vider le cache" ---
+    let window_clone_cache = window.clone();
+    clear_cache_button.connect_clicked(move |_| {
+        let result = AnalysisCache::default_path().and_then(|path| delete_cache_file(&path));
+        let message = match result {
+            Ok(()) => "Cache d'analyse vidé. La prochaine recherche relira tous les fichiers.".to_string(),
+            Err(e) => format!("Impossible de vider le cache : {}", e),
+        };
+        AlertDialog::builder().message(message).modal(true).build().show(Some(&window_clone_cache));
+    });
+
     // --- Logique Bouton "Chercher" ---
     let results_model_search = results_model.clone();
     let directory_store_search = directory_store.clone();
-    let recursive_checkbox_search = recursive_checkbox.clone();
+    let analysis_controls_search = analysis_controls.clone();
     let window_clone_search = window.clone();
-    search_button.connect_clicked(move |_| {
+    search_button.connect_clicked(move |button| {
         println!("Bouton 'Chercher Fichiers' cliqué");
         results_model_search.remove_all();
         let mut paths_to_scan: Vec<PathBuf> = Vec::new();
@@ -278,17 +431,54 @@ pub fn build_ui(app: &Application) {
             dialog.show(); // Déprécié
             return;
         }
-        let recursive = recursive_checkbox_search.is_active();
-        println!("Analyse demandée pour {} répertoires. Récursif: {}", paths_to_scan.len(), recursive);
+        let options = analysis_controls_search.options();
+        println!("Analyse demandée pour {} répertoires. Récursif: {}", paths_to_scan.len(), options.recursive);
         let results_model_clone = results_model_search.clone();
-        match analyze_multiple_directories(paths_to_scan, recursive) {
-            Ok(analysis_results) => {
-                println!("Analyse terminée par le core, {} résultats reçus.", analysis_results.len());
-                if analysis_results.is_empty() { println!("Aucun fichier trouvé."); }
-                for result in analysis_results {
-                    let item = FileDataItem::from_analysis(&result);
-                    results_model_clone.append(&item);
-                }
+        match analyze_streaming(paths_to_scan, options) {
+            Ok(stream) => {
+                // Remplissage progressif : lecture non bloquante du flux depuis la boucle GTK
+                button.set_sensitive(false);
+                let button = button.clone();
+                let mut items: Vec<FileDataItem> = Vec::new();
+                let mut finished = false;
+                glib::timeout_add_local(Duration::from_millis(50), move || {
+                    for _ in 0..STREAM_EVENTS_PER_TICK {
+                        match stream.receiver().try_recv() {
+                            Ok(AnalysisEvent::File(analysis)) => {
+                                let item = FileDataItem::from_analysis(&analysis);
+                                results_model_clone.append(&item);
+                                items.push(item);
+                            }
+                            Ok(AnalysisEvent::Error(e)) => eprintln!("Attention : {}", e),
+                            Ok(AnalysisEvent::Finished(updates)) => {
+                                for update in &updates {
+                                    if let Some(item) = items.get(update.index) { item.apply_duplicate_update(update); }
+                                }
+                                // Rafraîchit les lignes affichées (statut "Doublon")
+                                let n_items = results_model_clone.n_items();
+                                results_model_clone.items_changed(0, n_items, n_items);
+                                println!("Analyse terminée par le core, {} résultats reçus.", items.len());
+                                button.set_sensitive(true);
+                                finished = true;
+                            }
+                            // Hashes des fichiers restants, complétés après la détection des doublons
+                            Ok(AnalysisEvent::Hashes(updates)) => {
+                                for update in &updates {
+                                    if let Some(item) = items.get(update.index) { item.apply_duplicate_update(update); }
+                                }
+                                let n_items = results_model_clone.n_items();
+                                results_model_clone.items_changed(0, n_items, n_items);
+                            }
+                            Err(TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                            Err(TryRecvError::Disconnected) => {
+                                if !finished { eprintln!("Analyse interrompue avant la fin."); }
+                                button.set_sensitive(true);
+                                return glib::ControlFlow::Break;
+                            }
+                        }
+                    }
+                    glib::ControlFlow::Continue
+                });
             }
             Err(e) => {
                 eprintln!("Erreur globale lors de l'analyse des répertoires : {}", e);
@@ -337,65 +527,166 @@ pub fn build_ui(app: &Application) {
         println!("Fin 'Sélectionner si Date EXIF'");
     });
 
+    // --- Logique Bouton "Doublons" ---
+    duplicates_button.connect_clicked(clone!(#[weak] results_notebook, move |_| {
+        results_notebook.set_current_page(Some(duplicates_page));
+    }));
+
+    // --- Logique Bouton "Exporter" ---
+    let sort_model_export = sort_model.clone();
+    let directory_store_export = directory_store.clone();
+    let window_clone_export = window.clone();
+    let analysis_controls_export = analysis_controls.clone();
+    export_button.connect_clicked(move |_| {
+        let directories: Vec<PathBuf> = (0..directory_store_export.n_items())
+            .filter_map(|i| directory_store_export.item(i).and_then(|o| o.downcast::<StringObject>().ok()))
+            .map(|s| PathBuf::from(s.string()))
+            .collect();
+        export_results(&window_clone_export, sort_model_export.upcast_ref(), export_format_dropdown.selected(), directories, analysis_controls_export.options());
+    });
+
+    // --- Logique Bouton "Importer une session" ---
+    import_session_button.connect_clicked(clone!(#[weak] window, #[weak] results_model, #[weak] directory_store, #[strong] analysis_controls, move |_| {
+        import_session(&window, clone!(#[weak] window, #[weak] results_model, #[weak] directory_store, #[strong] analysis_controls, move |session| {
+            directory_store.remove_all();
+            for directory in &session.directories { directory_store.append(&StringObject::new(&directory.to_string_lossy())); }
+            analysis_controls.set_options(&session.options);
+            results_model.remove_all();
+            let mut missing = 0;
+            for file in &session.files {
+                if !file.path.is_file() { missing += 1; }
+                let item = FileDataItem::from_analysis(&file.analysis);
+                item.set_property("selected", file.selected);
+                if file.edited_name.is_some() { item.set_edited_name(file.edited_name.clone()); }
+                results_model.append(&item);
+            }
+            let mut detail = format!("{} fichier(s), analysés le {}.", session.files.len(), session.created.format("%d/%m/%Y à %H:%M"));
+            // Le renommage revérifie chaque fichier ; on prévient dès maintenant
+            if missing > 0 { detail.push_str(&format!("\n{} fichier(s) introuvable(s) sur le disque.", missing)); }
+            AlertDialog::builder().message("Session importée").detail(detail).modal(true).build().show(Some(&window));
+        }));
+    }));
+
     // --- Logique Bouton Renommer ---
     let model_rename = results_model.clone();
     let window_clone_rename = window.clone();
+    let set_file_times_check_rename = set_file_times_check.clone();
+    let loader_rename = thumbnail_loader.clone();
+    let removed_items_rename = removed_items.clone();
     rename_button.connect_clicked(move |_| {
         println!("Bouton 'Renommer Sélection' cliqué");
-        let mut items_to_rename: Vec<(PathBuf, PathBuf, u32)> = Vec::new();
-        let mut errors: Vec<String> = Vec::new(); let mut success_count = 0; let mut skipped_count = 0;
+        let mut items_to_rename: Vec<(PathBuf, PathBuf, u32, Option<CaptureDate>)> = Vec::new();
+        let mut errors: Vec<String> = Vec::new(); let mut skipped_count = 0;
+        // Fichiers cochés non renommés (nom inchangé ou sans nom proposé) : datés quand même si l'option est active
+        let mut unchanged_dates: Vec<FileTimesTarget> = Vec::new();
         for i in 0..model_rename.n_items() {
             if let Some(obj) = model_rename.item(i) {
                 if let Ok(item) = obj.downcast::<FileDataItem>() {
                     if item.property::<bool>("selected") {
                         let original_path = item.full_original_path();
                         let proposed_name = item.property::<String>("proposed-name");
+                        // Date de prise de vue et décalage EXIF (instant exact si le fuseau est connu)
+                        let date_taken = item.with_analysis(|a| a.exif.date_taken.map(|date| (date, a.exif.date_taken_offset()))).flatten();
+                        let mut skip = || { skipped_count += 1; if let Some((date, offset)) = date_taken { unchanged_dates.push((original_path.clone(), date, offset)); } };
                         if !proposed_name.is_empty() && proposed_name != "-" {
                             if let Some(parent_dir) = original_path.parent() {
                                 let new_path = parent_dir.join(&proposed_name);
-                                if original_path != new_path { items_to_rename.push((original_path.clone(), new_path, i)); }
-                                else { skipped_count += 1; }
+                                if original_path != new_path { items_to_rename.push((original_path.clone(), new_path, i, date_taken)); }
+                                else { skip(); }
                             } else { errors.push(format!("Pas de parent pour: {}", original_path.display())); }
-                        } else { skipped_count += 1; }
+                        } else { skip(); }
                     }
                 }
             }
         }
-        if items_to_rename.is_empty() && errors.is_empty() {
+        if items_to_rename.is_empty() && set_file_times_check_rename.is_active() && !unchanged_dates.is_empty() {
+            // Rien à renommer : seules les dates des fichiers cochés sont appliquées (lot à part, annulable)
+            let (message, detail) = match Journal::open_default() {
+                Ok(journal) => {
+                    let times_report = set_file_times_from_dates(&unchanged_dates, &journal);
+                    let mut detail = format!("Aucun fichier à renommer.\nFichiers datés : {}", times_report.updated);
+                    for err in times_report.errors.iter().take(10) { detail.push_str(&format!("\n- {}", err)); }
+                    ("Dates appliquées".to_string(), detail)
+                }
+                Err(e) => ("Dates non appliquées".to_string(), format!("Journal indisponible : {}", e)),
+            };
+            AlertDialog::builder().message(message).detail(detail).modal(true).build().show(Some(&window_clone_rename));
+            return;
+        }
+        if items_to_rename.is_empty() {
             // Utiliser AlertDialog à terme
             let dialog = MessageDialog::new( Some(&window_clone_rename), DialogFlags::MODAL | DialogFlags::DESTROY_WITH_PARENT, MessageType::Info, ButtonsType::Ok, "Aucun fichier valide sélectionné pour le renommage.");
             dialog.connect_response(|d, _| d.close()); dialog.show(); // Déprécié
             return;
         }
-        println!("Tentative de renommage de {} fichier(s)...", items_to_rename.len());
-        let mut indices_to_remove = Vec::new();
-        // Itérer en ordre inverse pour que les indices restent valides après suppression
-        for item_info in items_to_rename.iter().rev() {
-            let original_path = &item_info.0; let new_path = &item_info.1; let model_index = item_info.2;
-            println!("Renommage de {} -> {}", original_path.display(), new_path.display());
-            match fs::rename(original_path, new_path) {
-                Ok(_) => { success_count += 1; indices_to_remove.push(model_index); }
-                Err(e) => { let error_msg = format!("Erreur renommage '{}': {}", original_path.display(), e); eprintln!("{}", error_msg); errors.push(error_msg); }
+        let renames: Vec<(PathBuf, PathBuf)> = items_to_rename.iter().map(|info| (info.0.clone(), info.1.clone())).collect();
+        // Rien n'est modifié avant validation du plan complet par l'utilisateur
+        let model_rename = model_rename.clone();
+        let window_summary = window_clone_rename.clone();
+        let set_file_times_check_rename = set_file_times_check_rename.clone();
+        let loader_rename = loader_rename.clone();
+        let removed_items_rename = removed_items_rename.clone();
+        confirm_rename_plan(&window_clone_rename, renames, skipped_count, move |plan| {
+            println!("Tentative de renommage de {} fichier(s)...", items_to_rename.len());
+            let mut errors = errors.clone();
+            // Chaque renommage est journalisé : sans journal, rien n'est modifié
+            let journal = match Journal::open_default() {
+                Ok(journal) => journal,
+                Err(e) => {
+                    AlertDialog::builder().message("Renommage impossible").detail(format!("Journal indisponible : {}", e)).modal(true).build().show(Some(&window_summary));
+                    return;
+                }
+            };
+            let report = apply_rename_plan(&plan, &journal);
+            let success_count = report.renamed.len();
+            errors.extend(report.errors);
+            let renamed_from: HashSet<&PathBuf> = report.renamed.iter().map(|(from, _)| from).collect();
+            let renamed_infos: Vec<_> = items_to_rename.iter().filter(|info| renamed_from.contains(&info.0)).collect();
+            let mut indices_to_remove: Vec<u32> = renamed_infos.iter().map(|info| info.2).collect();
+            let renamed_paths: HashSet<PathBuf> = report.renamed.iter().flat_map(|(from, to)| [from.clone(), to.clone()]).collect();
+            // Option : dates du système de fichiers alignées sur la date de prise de vue, dans le lot du renommage (annulés ensemble),
+            // y compris pour les fichiers cochés dont le nom ne change pas
+            let mut file_times_targets: Vec<FileTimesTarget> = renamed_infos.iter().filter_map(|info| info.3.map(|(date, offset)| (info.1.clone(), date, offset))).collect();
+            file_times_targets.extend(unchanged_dates.iter().cloned());
+            let mut file_times_summary = String::new();
+            if set_file_times_check_rename.is_active() && !file_times_targets.is_empty() {
+                let batch_id = report.batch_id.clone().unwrap_or_else(new_batch_id);
+                let times_report = set_file_times_in_batch(&file_times_targets, &journal, &batch_id);
+                file_times_summary = format!("Fichiers datés : {}\n", times_report.updated);
+                errors.extend(times_report.errors);
             }
-        }
-        // Supprimer les éléments renommés du modèle (ordre inverse déjà géré par l'itération précédente)
-        indices_to_remove.sort_unstable(); // Trier les indices pour la suppression
-        indices_to_remove.reverse(); // Supprimer de la fin vers le début
-        for index in &indices_to_remove { model_rename.remove(*index); }
-
-        // Afficher le résumé
-        let mut summary = format!("Renommage terminé.\n\nSuccès : {}\nÉchecs : {}\nSkippés : {}\n", success_count, errors.len(), skipped_count);
-        if !errors.is_empty() {
-            summary.push_str("\nDétails des erreurs :\n");
-            for err in errors.iter().take(10) { summary.push_str(&format!("- {}\n", err)); }
-            if errors.len() > 10 { summary.push_str("...\n"); }
-        }
-        // Utiliser AlertDialog à terme
-        let dialog = MessageDialog::new( Some(&window_clone_rename), DialogFlags::MODAL | DialogFlags::DESTROY_WITH_PARENT, if errors.is_empty() { MessageType::Info } else { MessageType::Warning }, ButtonsType::Ok, &summary);
-        dialog.connect_response(|d, _| d.close()); dialog.show(); // Déprécié
-        println!("Fin 'Renommer Sélection'");
+            // Retirer les éléments renommés du modèle (conservés pour les remettre en cas d'annulation)
+            indices_to_remove.sort_unstable(); // Trier les indices pour la suppression
+            indices_to_remove.reverse(); // Supprimer de la fin vers le début
+            for index in &indices_to_remove {
+                if let Some(item) = model_rename.item(*index).and_then(|o| o.downcast::<FileDataItem>().ok()) { removed_items_rename.remember(item); }
+                model_rename.remove(*index);
+            }
+            loader_rename.forget(&renamed_paths);
+
+            // Afficher le résumé
+            let mut summary = format!("Renommage terminé.\n\nSuccès : {}\nÉchecs : {}\nSkippés : {}\n{}", success_count, errors.len(), skipped_count, file_times_summary);
+            if success_count > 0 { summary.push_str("\nAnnulable via « Annuler le dernier renommage ».\n"); }
+            if !errors.is_empty() {
+                summary.push_str("\nDétails des erreurs :\n");
+                for err in errors.iter().take(10) { summary.push_str(&format!("- {}\n", err)); }
+                if errors.len() > 10 { summary.push_str("...\n"); }
+            }
+            // Utiliser AlertDialog à terme
+            let dialog = MessageDialog::new( Some(&window_summary), DialogFlags::MODAL | DialogFlags::DESTROY_WITH_PARENT, if errors.is_empty() { MessageType::Info } else { MessageType::Warning }, ButtonsType::Ok, &summary);
+            dialog.connect_response(|d, _| d.close()); dialog.show(); // Déprécié
+            println!("Fin 'Renommer Sélection'");
+        });
      });
 
+    // --- Logique Boutons "Annuler le dernier renommage" / "Historique" ---
+    undo_rename_button.connect_clicked(clone!(#[weak] window, #[weak] results_model, #[strong] removed_items, move |_| {
+        undo_last_rename(&window, &results_model, &removed_items);
+    }));
+    history_button.connect_clicked(clone!(#[weak] window, #[weak] results_model, #[strong] removed_items, move |_| {
+        show_history_dialog(&window, &results_model, &removed_items);
+    }));
+
     // --- Logique de Filtrage ---
     let filter_state = StdRc::new(StdRefCell::new(FilterState::default()));
 
@@ -431,7 +722,11 @@ pub fn build_ui(app: &Application) {
             true // Montrer si pas filtré
         } else { true } // Montrer si pas FileDataItem
     });
-    filter_model.set_filter(Some(&custom_filter));
+    // Filtres de la grille et facettes appliqués ensemble
+    let combined_filter = EveryFilter::new();
+    combined_filter.append(custom_filter.clone());
+    combined_filter.append(facet_filters.filter().clone());
+    filter_model.set_filter(Some(&combined_filter));
 
 
     // --- Closure pour mettre à jour l'état et déclencher le refiltrage ---